@@ -1,7 +1,5 @@
-use serde::{Deserialize, Serialize};
 use sycamore::prelude::*;
-use sycamore_dnd::{create_droppable, RawTransfer};
-use wasm_bindgen_futures::JsFuture;
+use sycamore_dnd::{create_drop_guard, create_droppable, DroppedFiles};
 
 fn main() {
     console_error_panic_hook::set_once();
@@ -14,46 +12,19 @@ fn main() {
     });
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
-struct ContentItem {
-    id: i32,
-    name: String,
-}
-
 #[component]
 fn App<G: Html>(cx: Scope) -> View<G> {
-    let text = create_ref(cx, create_rc_signal("Drop here".to_string()));
+    let text = create_signal(cx, "Drop here".to_string());
+    create_drop_guard::<G>(cx);
 
     let drop = create_droppable(cx)
-        .accept(|transfer: &RawTransfer| {
-            let items = transfer.items();
-            if items.length() == 1 && items.get(0).unwrap().kind() == "file" {
-                true
-            } else if let Some(files) = transfer.files() {
-                files.length() == 1
-            } else {
-                false
-            }
-        })
-        .on_drop({
-            move |transfer: RawTransfer| {
-                let file = transfer
-                    .items()
-                    .get(0)
-                    .map(|item| item.get_as_file().unwrap().unwrap())
-                    .or_else(|| transfer.files().unwrap().get(0))
-                    .unwrap();
-                let text = text.clone();
-                wasm_bindgen_futures::spawn_local(async move {
-                    let file_text = JsFuture::from(file.text())
-                        .await
-                        .unwrap()
-                        .as_string()
-                        .unwrap();
-                    text.set(file_text);
-                });
+        .max_count(1)
+        .on_drop_async(move |files: DroppedFiles| async move {
+            if let Some(file) = files.first() {
+                text.set(file.read_text().await.unwrap());
             }
         })
+        .pending_class("loading")
         .build();
 
     view! { cx,
@@ -1,5 +1,5 @@
 use sycamore::prelude::*;
-use sycamore_dnd::{create_draggable, create_droppable};
+use sycamore_dnd::{create_draggable, create_droppable, DragBackend};
 
 fn main() {
     console_error_panic_hook::set_once();
@@ -23,7 +23,10 @@ fn App<G: Html>(cx: Scope) -> View<G> {
         .on_drop(move |_: ()| inside.set(false))
         .hovering_class("drag-over")
         .build();
-    let drag = create_draggable(cx).dragging_class("dragging").build();
+    let drag = create_draggable(cx)
+        .dragging_class("dragging")
+        .backend(DragBackend::Auto)
+        .build();
 
     view! { cx,
         div(class = "container") {
@@ -0,0 +1,58 @@
+use sycamore::prelude::*;
+use sycamore_dnd::create_sortable;
+
+fn main() {
+    console_error_panic_hook::set_once();
+    wasm_logger::init(wasm_logger::Config::default());
+    sycamore::render(|cx| {
+        view! { cx,
+            p { "Hello, World!" }
+            App()
+        }
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ContentItem {
+    id: i32,
+    name: String,
+}
+
+#[component]
+fn App<G: Html>(cx: Scope) -> View<G> {
+    let contents = create_signal(
+        cx,
+        (0..5)
+            .map(|id| ContentItem {
+                id,
+                name: format!("Test item {id}"),
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let sortable = create_sortable(cx, contents, |item| item.id)
+        .dragging_class("dragging")
+        .before_class("drop-before")
+        .after_class("drop-after")
+        .on_reorder(|from, to| log::info!("Moved item from {from} to {to}"))
+        .build();
+
+    view! { cx,
+        div(class = "container") {
+            div(class="box") {
+                Keyed(
+                    iterable=contents,
+                    view=move |cx, item| {
+                        let node = sortable.item(cx, &item);
+                        view! { cx,
+                            div(class = "item", ref = node) {
+                                (item.name)
+                            }
+                        }
+                    },
+                    key=|item| item.id,
+                )
+            }
+        }
+    }
+}
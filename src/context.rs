@@ -0,0 +1,171 @@
+use crate::{transfer_types, FromTransfer};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use sycamore::prelude::*;
+use web_sys::{DataTransfer, Element};
+
+/// The prefix of the MIME type used to store the id of an in-memory drag in a [`DataTransfer`].
+const DRAG_ID_PREFIX: &str = "application/x-sycamore-dnd-id+";
+
+thread_local! {
+    static NEXT_DRAG_ID: Cell<u64> = const { Cell::new(0) };
+    static DEFAULT_CONTEXT: DndContext = DndContext::new();
+}
+
+/// A function that produces a fresh copy of the dragged value.
+pub(crate) type ValueFn = Rc<dyn Fn() -> Box<dyn Any>>;
+
+/// Shared state for the draggables and droppables of an app.
+///
+/// The context keeps the value set with [`DraggableBuilder::value`](crate::DraggableBuilder::value)
+/// in memory while it's being dragged, so droppables using the same context receive the original
+/// Rust value instead of parsing it from the [`DataTransfer`]. Since the value is never serialized,
+/// it can be anything - including types like `Rc`, signals or closures. Droppables can also run
+/// their `accept` callback on the value while hovering.
+///
+/// A default context shared by the whole thread is used unless one is provided with
+/// [`provide_dnd_context`].
+#[derive(Clone)]
+pub struct DndContext {
+    active: Rc<RefCell<Option<ActiveDrag>>>,
+    /// A copy of the [`DataTransfer`] of the active drag, which all mounted droppables subscribe
+    /// to, to show whether they would accept the item.
+    transfer: RcSignal<Option<DataTransfer>>,
+}
+
+struct ActiveDrag {
+    id: u64,
+    value: Option<ValueFn>,
+    drop: Option<DropRecord>,
+}
+
+/// What happened when the item of a drag was dropped on a droppable.
+pub(crate) struct DropRecord {
+    /// The droppable the item was dropped on
+    pub(crate) target: Element,
+    /// The error the droppable rejected the item with, if any
+    pub(crate) error: Option<String>,
+}
+
+impl DndContext {
+    fn new() -> Self {
+        Self {
+            active: Default::default(),
+            transfer: create_rc_signal(None),
+        }
+    }
+
+    /// Register a new drag and write its id to the [`DataTransfer`].
+    pub(crate) fn start_drag(&self, transfer: &DataTransfer, value: Option<ValueFn>) -> u64 {
+        let id = NEXT_DRAG_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        });
+        transfer
+            .set_data(&format!("{DRAG_ID_PREFIX}{id}"), &id.to_string())
+            .unwrap();
+        *self.active.borrow_mut() = Some(ActiveDrag {
+            id,
+            value,
+            drop: None,
+        });
+        self.transfer.set(snapshot(transfer));
+        id
+    }
+
+    /// Clear the drag with the given id, if it's still active. Returns what happened when it was
+    /// dropped on a droppable, if it was. Ending a drag again does nothing and returns `None`.
+    pub(crate) fn end_drag(&self, id: u64) -> Option<DropRecord> {
+        let drag = {
+            let mut active = self.active.borrow_mut();
+            if active.as_ref().map(|drag| drag.id) != Some(id) {
+                return None;
+            }
+            active.take()?
+        };
+        // Notify the droppables only once the drag is no longer borrowed.
+        self.transfer.set(None);
+        drag.drop
+    }
+
+    /// Record the droppable the drag the [`DataTransfer`] belongs to was dropped on, and the error
+    /// it rejected the item with.
+    pub(crate) fn set_drop(&self, transfer: &DataTransfer, target: Element, error: Option<String>) {
+        let Some(id) = drag_id(transfer) else {
+            return;
+        };
+        if let Some(drag) = self
+            .active
+            .borrow_mut()
+            .as_mut()
+            .filter(|drag| drag.id == id)
+        {
+            drag.drop = Some(DropRecord { target, error });
+        }
+    }
+
+    /// Get the in-memory value of the drag the [`DataTransfer`] belongs to, if it was started in
+    /// this context and has a value of type `T`.
+    pub(crate) fn value<T: FromTransfer + 'static>(&self, transfer: &DataTransfer) -> Option<T> {
+        let id = drag_id(transfer)?;
+        let active = self.active.borrow();
+        let value = active
+            .as_ref()
+            .filter(|drag| drag.id == id)?
+            .value
+            .as_ref()?;
+        T::from_value(value())
+    }
+
+    /// A copy of the [`DataTransfer`] of the drag in progress, if there is one. Unlike the one
+    /// passed to drag events, its data can be read at any time.
+    pub(crate) fn active_transfer(&self) -> Option<DataTransfer> {
+        self.transfer.get().as_ref().clone()
+    }
+
+    /// Read a `T` from the [`DataTransfer`], preferring the in-memory value if there is one.
+    pub(crate) fn read<T: FromTransfer + 'static>(&self, transfer: &DataTransfer) -> Option<T> {
+        self.value(transfer).or_else(|| T::from_transfer(transfer))
+    }
+}
+
+/// Copy the data of a [`DataTransfer`] while it can still be read, which browsers only allow in
+/// `dragstart` and `drop`. Files aren't copied. Returns `None` if the browser can't construct a
+/// [`DataTransfer`], in which case droppables don't show whether they would accept the item.
+fn snapshot(transfer: &DataTransfer) -> Option<DataTransfer> {
+    let snapshot = DataTransfer::new().ok()?;
+    snapshot.set_effect_allowed(&transfer.effect_allowed());
+    for ty in transfer_types(transfer).iter().filter(|ty| *ty != "Files") {
+        if let Ok(data) = transfer.get_data(ty) {
+            snapshot.set_data(ty, &data).ok();
+        }
+    }
+    Some(snapshot)
+}
+
+/// Read the drag id from the types of a [`DataTransfer`]. The id is stored in the type itself
+/// because browsers only expose the types while dragging.
+pub(crate) fn drag_id(transfer: &DataTransfer) -> Option<u64> {
+    transfer_types(transfer)
+        .iter()
+        .find_map(|ty| ty.strip_prefix(DRAG_ID_PREFIX)?.parse().ok())
+}
+
+/// Provide a new [`DndContext`] for this scope and its children. This is only needed to keep drags
+/// in separate parts of an app isolated from each other, otherwise a shared default context is
+/// used.
+pub fn provide_dnd_context(cx: Scope<'_>) -> &DndContext {
+    provide_context(cx, DndContext::new())
+}
+
+/// Get the [`DndContext`] provided by a parent scope, or the shared default context if there is
+/// none.
+pub fn use_dnd_context(cx: Scope<'_>) -> DndContext {
+    try_use_context::<DndContext>(cx)
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_CONTEXT.with(Clone::clone))
+}
@@ -1,18 +1,83 @@
-use crate::{AsTransfer, DropEffect};
-use sycamore::{prelude::*, web::html::ev};
+use crate::{
+    context::{drag_id as transfer_drag_id, ValueFn},
+    guard::add_global_listener,
+    pointer::create_pointer_drag,
+    set_if_changed, use_dnd_context, AsTransfer, DragBackend, DropEffect,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use sycamore::{
+    prelude::*,
+    web::{html::ev, render_get_scope},
+};
 use wasm_bindgen::JsCast;
-use web_sys::{DataTransfer, DragEvent, Element};
+use web_sys::{DataTransfer, DragEvent, Element, Event, HtmlElement, Node, PointerEvent};
+
+/// The result of a drag, passed to the `on_drag_end` callback of a draggable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DragOutcome {
+    /// Whether the item was dropped on an element that accepted it.
+    pub accepted: bool,
+    /// The effect of the drop, parsed from the `dropEffect` of the [`DataTransfer`]. This is
+    /// [`DropEffect::None`] if the drop was cancelled or rejected.
+    pub effect: DropEffect,
+    /// The droppable that received the item, if it was dropped on a droppable in the same
+    /// [`DndContext`](crate::DndContext).
+    pub target: Option<Element>,
+    /// The error the droppable's `on_drop` callback rejected the item with, if it did.
+    pub error: Option<String>,
+}
+
+/// The source of the data written to the [`DataTransfer`] when a drag starts.
+enum DragData<'cx, T> {
+    Value(T),
+    Fn(Box<dyn Fn() -> T + 'cx>),
+    Signal(&'cx ReadSignal<T>),
+}
+
+impl<T: AsTransfer> DragData<'_, T> {
+    fn write_to_transfer(&self, transfer: &DataTransfer) {
+        match self {
+            DragData::Value(data) => data.write_to_transfer(transfer),
+            DragData::Fn(f) => f().write_to_transfer(transfer),
+            DragData::Signal(signal) => T::write_to_transfer(&signal.get_untracked(), transfer),
+        }
+    }
+}
+
+/// The part of a draggable element that starts a drag.
+enum DragHandle<'cx, G: Html> {
+    Node(&'cx NodeRef<G>),
+    Selector(String),
+}
 
 /// The builder used to configure a draggable element
 pub struct DraggableBuilder<'cx, G: Html, T: AsTransfer + 'static> {
     scope: Scope<'cx>,
-    data: Option<T>,
+    data: Option<DragData<'cx, T>>,
     #[allow(clippy::type_complexity)]
     set_data: Option<Box<dyn Fn(&DataTransfer) + 'cx>>,
+    /// Creates the in-memory value when a drag starts
+    value: Option<Box<dyn Fn() -> ValueFn + 'cx>>,
     dragging_class: String,
     allowed_effect: DropEffect,
     drag_image: Option<(Element, i32, i32)>,
+    #[allow(clippy::type_complexity)]
+    drag_view: Option<(Box<dyn Fn(Scope<'_>) -> View<DomNode> + 'cx>, i32, i32)>,
     node_ref: Option<&'cx NodeRef<G>>,
+    backend: DragBackend,
+    handle: Option<DragHandle<'cx, G>>,
+    on_drag_start: Option<Box<dyn Fn() + 'cx>>,
+    #[allow(clippy::type_complexity)]
+    on_drag: Option<Box<dyn Fn(&DragEvent) + 'cx>>,
+    drag_throttle: f64,
+    on_drag_end: Option<Box<dyn Fn(DragOutcome) + 'cx>>,
+    disabled: Option<&'cx ReadSignal<bool>>,
+    is_dragging: &'cx Signal<bool>,
 }
 
 impl<'cx, G: Html, T: AsTransfer> DraggableBuilder<'cx, G, T> {
@@ -21,24 +86,70 @@ impl<'cx, G: Html, T: AsTransfer> DraggableBuilder<'cx, G, T> {
             scope,
             data: None,
             set_data: None,
+            value: None,
             dragging_class: Default::default(),
             allowed_effect: Default::default(),
             drag_image: None,
+            drag_view: None,
             node_ref: None,
+            backend: Default::default(),
+            handle: None,
+            on_drag_start: None,
+            on_drag: None,
+            drag_throttle: 50.0,
+            on_drag_end: None,
+            disabled: None,
+            is_dragging: create_signal(scope, false),
         }
     }
 
     /// Sets the data that gets serialized to the [`DataTransfer`]. If you need to do custom
-    /// serialization, use `set_data` instead.
+    /// serialization, use `set_data` instead. The value is captured when this is called, use
+    /// `data_fn` or `data_signal` if it can change.
     pub fn data<Data: AsTransfer>(self, data: Data) -> DraggableBuilder<'cx, G, Data> {
+        self.with_data(DragData::Value(data))
+    }
+
+    /// Sets a function that computes the data when a drag starts. The result gets serialized to
+    /// the [`DataTransfer`].
+    pub fn data_fn<Data: AsTransfer>(
+        self,
+        f: impl Fn() -> Data + 'cx,
+    ) -> DraggableBuilder<'cx, G, Data> {
+        self.with_data(DragData::Fn(Box::new(f)))
+    }
+
+    /// Sets a signal whose current value gets serialized to the [`DataTransfer`] when a drag
+    /// starts.
+    pub fn data_signal<Data: AsTransfer>(
+        self,
+        signal: &'cx ReadSignal<Data>,
+    ) -> DraggableBuilder<'cx, G, Data> {
+        self.with_data(DragData::Signal(signal))
+    }
+
+    fn with_data<Data: AsTransfer>(
+        self,
+        data: DragData<'cx, Data>,
+    ) -> DraggableBuilder<'cx, G, Data> {
         DraggableBuilder {
             data: Some(data),
             dragging_class: self.dragging_class,
             allowed_effect: self.allowed_effect,
             set_data: self.set_data,
+            value: self.value,
             scope: self.scope,
             node_ref: self.node_ref,
             drag_image: self.drag_image,
+            drag_view: self.drag_view,
+            backend: self.backend,
+            handle: self.handle,
+            on_drag_start: self.on_drag_start,
+            on_drag: self.on_drag,
+            drag_throttle: self.drag_throttle,
+            on_drag_end: self.on_drag_end,
+            disabled: self.disabled,
+            is_dragging: self.is_dragging,
         }
     }
 
@@ -49,6 +160,50 @@ impl<'cx, G: Html, T: AsTransfer> DraggableBuilder<'cx, G, T> {
         self
     }
 
+    /// Sets a value that is kept in memory by the [`DndContext`](crate::DndContext) while it's
+    /// being dragged. Droppables in the same context receive a clone of the original value without
+    /// any serialization, so this works for types like `Rc`, signals or closures. Use
+    /// [`InMemory`](crate::InMemory) to receive types that don't implement
+    /// [`FromTransfer`](crate::FromTransfer).
+    ///
+    /// The value isn't visible outside of the app. Combine it with `data` or `set_data` if other
+    /// applications should receive something as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn Actions<G: Html>(cx: Scope) -> View<G> {
+    ///     // Closures can't be serialized, so this can only be dragged in memory.
+    ///     let action: Rc<dyn Fn()> = Rc::new(|| log::info!("Running the action"));
+    ///     let drag = create_draggable(cx).value(action).build();
+    ///     let drop = create_droppable(cx)
+    ///         .on_drop(|action: InMemory<Rc<dyn Fn()>>| (action.0)())
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "action", ref = drag) { "Drag me" }
+    ///         div(class = "run", ref = drop) { "Drop here to run" }
+    ///     }
+    /// }
+    /// ```
+    pub fn value<V: Clone + 'static>(self, value: V) -> Self {
+        self.value_fn(move || value.clone())
+    }
+
+    /// Sets a function that computes the in-memory value when a drag starts, so droppables
+    /// receive the value at that point instead of when the draggable was created. See `value`.
+    pub fn value_fn<V: Clone + 'static>(mut self, f: impl Fn() -> V + 'cx) -> Self {
+        self.value = Some(Box::new(move || {
+            let value = f();
+            Rc::new(move || Box::new(value.clone()) as Box<dyn Any>) as ValueFn
+        }));
+        self
+    }
+
     /// Set a class or class list to be added when the element is being dragged. The class is
     /// automatically removed when the drag ends.
     pub fn dragging_class(mut self, class: impl Into<String>) -> Self {
@@ -70,7 +225,8 @@ impl<'cx, G: Html, T: AsTransfer> DraggableBuilder<'cx, G, T> {
     }
 
     /// Sets an HTML element to be shown when dragging the item. Creating this can be annoying - if
-    /// an image is all that's needed use `drag_image` instead.
+    /// an image is all that's needed use `drag_image` instead, or use `drag_view` to render a
+    /// view.
     pub fn drag_element(mut self, element: impl JsCast, x_offset: i32, y_offset: i32) -> Self {
         self.drag_image = Some((element.unchecked_into::<Element>(), x_offset, y_offset));
         self
@@ -84,6 +240,159 @@ impl<'cx, G: Html, T: AsTransfer> DraggableBuilder<'cx, G, T> {
         self.drag_element(image, x_offset, y_offset)
     }
 
+    /// Renders a view to be shown when dragging the item. The view is rendered off-screen every
+    /// time a drag starts and disposed when it ends, so it can show the current state, like the
+    /// number of selected items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn Item<G: Html>(cx: Scope) -> View<G> {
+    ///     let selected = create_signal(cx, 3);
+    ///     let drag = create_draggable(cx)
+    ///         .drag_view(
+    ///             move |cx| {
+    ///                 let count = *selected.get();
+    ///                 view! { cx, div(class = "badge") { (count) " items" } }
+    ///             },
+    ///             10,
+    ///             10,
+    ///         )
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "item", ref = drag) {
+    ///             "Drag me"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn drag_view(
+        mut self,
+        view: impl Fn(Scope<'_>) -> View<DomNode> + 'cx,
+        x_offset: i32,
+        y_offset: i32,
+    ) -> Self {
+        self.drag_view = Some((Box::new(view), x_offset, y_offset));
+        self
+    }
+
+    /// Set the [`DragBackend`] used to drive the drag. Defaults to [`DragBackend::Native`], use
+    /// [`DragBackend::Pointer`] or [`DragBackend::Auto`] to support touch devices.
+    pub fn backend(mut self, backend: DragBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Only start dragging when the pointer went down on the handle element, a child of the
+    /// draggable. The rest of the element behaves normally, so text can be selected and inputs can
+    /// be used. The dragging class and `data-dragging` attribute are still applied to the whole
+    /// draggable element.
+    pub fn handle(mut self, handle: &'cx NodeRef<G>) -> Self {
+        self.handle = Some(DragHandle::Node(handle));
+        self
+    }
+
+    /// Like `handle`, but uses any child matching a CSS selector as a handle.
+    pub fn handle_selector(mut self, selector: impl Into<String>) -> Self {
+        self.handle = Some(DragHandle::Selector(selector.into()));
+        self
+    }
+
+    /// Sets a callback to run when the element starts being dragged.
+    pub fn on_drag_start(mut self, f: impl Fn() + 'cx) -> Self {
+        self.on_drag_start = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback to run while the element is being dragged. Calls are throttled to at most
+    /// one every 50ms by default, see `drag_throttle`.
+    pub fn on_drag(mut self, f: impl Fn(&DragEvent) + 'cx) -> Self {
+        self.on_drag = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the minimum time in milliseconds between two calls of the `on_drag` callback.
+    pub fn drag_throttle(mut self, millis: u32) -> Self {
+        self.drag_throttle = millis as f64;
+        self
+    }
+
+    /// Sets a callback to run when the drag ends, whether the item was dropped or not. The
+    /// [`DragOutcome`] can be used to remove the item from its source after a successful move.
+    pub fn on_drag_end(mut self, f: impl Fn(DragOutcome) + 'cx) -> Self {
+        self.on_drag_end = Some(Box::new(f));
+        self
+    }
+
+    /// Disable dragging while the signal is `true`. A disabled draggable isn't `draggable`,
+    /// ignores pointer events and has `aria-disabled` set.
+    pub fn disabled(mut self, disabled: &'cx ReadSignal<bool>) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// A signal that is `true` while the element is being dragged.
+    pub fn is_dragging(&self) -> &'cx ReadSignal<bool> {
+        self.is_dragging
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.disabled
+            .map(|disabled| *disabled.get_untracked())
+            .unwrap_or(false)
+    }
+
+    /// Check whether an event happened on the drag handle. Always `true` if there is no handle.
+    fn is_on_handle(&self, node: &G, e: &Event) -> bool {
+        let Some(handle) = self.handle.as_ref() else {
+            return true;
+        };
+        let Some(target) = e
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+        else {
+            return false;
+        };
+        match handle {
+            DragHandle::Node(handle) => handle
+                .try_get_raw()
+                .map(|handle| handle.to_web_sys().contains(Some(&target)))
+                .unwrap_or(false),
+            DragHandle::Selector(selector) => target
+                .closest(selector)
+                .ok()
+                .flatten()
+                .map(|handle| node.to_web_sys().contains(Some(&handle)))
+                .unwrap_or(false),
+        }
+    }
+
+    /// The elements that start a drag when touched.
+    fn handle_elements(&self, node: &G) -> Vec<HtmlElement> {
+        let elements = match self.handle.as_ref() {
+            None => vec![node.to_web_sys()],
+            Some(DragHandle::Node(handle)) => handle
+                .try_get_raw()
+                .map(|handle| handle.to_web_sys())
+                .into_iter()
+                .collect(),
+            Some(DragHandle::Selector(selector)) => node
+                .to_web_sys()
+                .unchecked_into::<Element>()
+                .query_selector_all(selector)
+                .map(|list| (0..list.length()).filter_map(|i| list.get(i)).collect())
+                .unwrap_or_default(),
+        };
+        elements
+            .into_iter()
+            .filter_map(|element: Node| element.dyn_into().ok())
+            .collect()
+    }
+
     /// Creates the dragging effects and returns a [`NodeRef`] that needs to be set as the `ref`
     /// attribute on the draggable element.
     pub fn build(self) -> &'cx NodeRef<G> {
@@ -119,6 +428,41 @@ pub fn create_draggable<G: Html>(cx: Scope<'_>) -> DraggableBuilder<'_, G, ()> {
     DraggableBuilder::new(cx)
 }
 
+/// Creates the off-screen element drag views are rendered into. The browser takes a snapshot of
+/// the drag image when the drag starts, so it needs to be rendered, just not visible.
+fn create_drag_view_container() -> Element {
+    let container = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("div").ok())
+        .unwrap();
+    container
+        .set_attribute("style", "position:fixed;top:0;left:-10000px;")
+        .unwrap();
+    container
+}
+
+/// Dispose the scope of a rendered drag view, if there is one, and remove its nodes from the
+/// container.
+fn dispose_drag_view(scope: &RefCell<Option<ScopeDisposer>>, container: Option<&Element>) {
+    if let Some(scope) = scope.take() {
+        // SAFETY: The drag view scope is not accessed after being disposed.
+        unsafe { scope.dispose() };
+    }
+    if let Some(container) = container {
+        container.set_inner_html("");
+    }
+}
+
+/// Render a drag view into its container, replacing the one from the previous drag.
+fn render_drag_view<'a>(
+    view: &'a dyn Fn(Scope<'_>) -> View<DomNode>,
+    container: &'a Element,
+    scope: &RefCell<Option<ScopeDisposer<'a>>>,
+) {
+    dispose_drag_view(scope, Some(container));
+    *scope.borrow_mut() = Some(render_get_scope(view, container));
+}
+
 fn create_draggable_effect<'cx, G: Html, T: AsTransfer + 'static>(
     cx: Scope<'cx>,
     options: DraggableBuilder<'cx, G, T>,
@@ -127,12 +471,47 @@ fn create_draggable_effect<'cx, G: Html, T: AsTransfer + 'static>(
     // SAFETY: This is safe as long as the builder has no custom `Drop` implementation
     // See documentation for `create_ref_unsafe`.
     let options = unsafe { create_ref_unsafe(cx, options) };
+    let context = create_ref(cx, use_dnd_context(cx));
+    let drag_id = create_ref(cx, Cell::new(None));
+    let on_handle = create_ref(cx, Cell::new(options.handle.is_none()));
+    let last_drag = create_ref(cx, Cell::new(f64::NEG_INFINITY));
+    let drag_image = create_ref(cx, RefCell::new(None));
+    let drag_view_container = (G::IS_BROWSER && options.drag_view.is_some())
+        .then(|| create_ref(cx, create_drag_view_container()));
+    // SAFETY: `ScopeDisposer` has no custom `Drop` implementation. See documentation for
+    // `create_ref_unsafe`.
+    let drag_view_scope = unsafe { create_ref_unsafe(cx, RefCell::new(None::<ScopeDisposer>)) };
+    // Set by the document-level `drop` and `dragend` listeners, which are only added once the
+    // element was dragged.
+    let drag_ended = create_ref(cx, create_rc_signal(None::<DragEvent>));
+    let listening = create_ref(cx, Cell::new(false));
+    on_cleanup(cx, move || {
+        dispose_drag_view(drag_view_scope, drag_view_container);
+        // The element can be removed by a drop before its drag ended.
+        if let Some(id) = drag_id.take() {
+            context.end_drag(id);
+        }
+    });
+    let backend = if G::IS_BROWSER {
+        options.backend.resolve()
+    } else {
+        DragBackend::Native
+    };
 
     create_effect(cx, move || {
         if let Some(node) = node_ref.try_get_raw() {
             let on_drag_start = {
                 let node = node.clone();
                 move |e: DragEvent| {
+                    if options.is_disabled() {
+                        e.prevent_default();
+                        return;
+                    }
+                    if !on_handle.get() {
+                        log::trace!("Drag started outside of the handle, cancelling");
+                        e.prevent_default();
+                        return;
+                    }
                     log::trace!("Drag start");
 
                     let transfer = e.data_transfer().unwrap();
@@ -143,31 +522,199 @@ fn create_draggable_effect<'cx, G: Html, T: AsTransfer + 'static>(
                     if let Some(set_data) = options.set_data.as_ref() {
                         set_data(&transfer);
                     }
-                    if options.data.is_none() && options.set_data.is_none() {
+                    if options.data.is_none()
+                        && options.set_data.is_none()
+                        && options.value.is_none()
+                    {
                         ().write_to_transfer(&transfer);
                     }
-                    if let Some((image, offset_x, offset_y)) = options.drag_image.as_ref() {
+                    drag_id.set(Some(
+                        context.start_drag(&transfer, options.value.as_ref().map(|value| value())),
+                    ));
+                    if !listening.replace(true) {
+                        if let Some(document) =
+                            web_sys::window().and_then(|window| window.document())
+                        {
+                            for event in ["drop", "dragend"] {
+                                let drag_ended = drag_ended.clone();
+                                add_global_listener(cx, &document, event, move |e| {
+                                    drag_ended.set(Some(e));
+                                });
+                            }
+                        }
+                    }
+
+                    let image = match (options.drag_view.as_ref(), drag_view_container) {
+                        (Some((view, x_offset, y_offset)), Some(container)) => {
+                            web_sys::window()
+                                .and_then(|window| window.document())
+                                .and_then(|document| document.body())
+                                .map(|body| body.append_child(container));
+                            render_drag_view(&**view, container, drag_view_scope);
+                            Some((container.clone(), *x_offset, *y_offset))
+                        }
+                        _ => options.drag_image.clone(),
+                    };
+                    if let Some((image, offset_x, offset_y)) = image.as_ref() {
                         transfer.set_drag_image(image, *offset_x, *offset_y);
                     }
+                    *drag_image.borrow_mut() = image;
 
                     node.add_class(&options.dragging_class);
                     node.set_attribute("data-dragging".into(), "".into());
+                    options.is_dragging.set(true);
+                    if let Some(on_drag_start) = options.on_drag_start.as_ref() {
+                        on_drag_start();
+                    }
+                }
+            };
+
+            let on_drag = move |e: DragEvent| {
+                if let Some(on_drag) = options.on_drag.as_ref() {
+                    let now = js_sys::Date::now();
+                    if now - last_drag.get() >= options.drag_throttle {
+                        last_drag.set(now);
+                        on_drag(&e);
+                    }
                 }
             };
 
-            let on_drag_end = {
+            let on_drag_end = Rc::new({
                 let node = node.clone();
-                move |_: DragEvent| {
+                move |e: &DragEvent| {
+                    let Some(id) = drag_id.take() else {
+                        return;
+                    };
                     log::trace!("Drag end");
+                    let drop = context.end_drag(id);
+                    dispose_drag_view(drag_view_scope, drag_view_container);
+                    if let Some(container) = drag_view_container {
+                        container.remove();
+                    }
 
                     node.remove_class(&options.dragging_class);
                     node.remove_attribute("data-dragging".into());
+                    options.is_dragging.set(false);
+                    if options.handle.is_some() {
+                        node.remove_attribute("draggable".into());
+                    }
+
+                    if let Some(on_drag_end) = options.on_drag_end.as_ref() {
+                        let effect = e
+                            .data_transfer()
+                            .map(|transfer| DropEffect::from_js(&transfer.drop_effect()))
+                            .unwrap_or(DropEffect::None);
+                        let (target, error) = drop
+                            .map(|drop| (Some(drop.target), drop.error))
+                            .unwrap_or_default();
+                        on_drag_end(DragOutcome {
+                            accepted: effect != DropEffect::None && error.is_none(),
+                            effect,
+                            target,
+                            error,
+                        });
+                    }
+                }
+            });
+            // Browsers don't send `dragend` to an element that was removed or moved by the drop,
+            // like a reordered item of a `Keyed` list, so also end the drag once the drop reached
+            // the document. Whichever comes first ends it.
+            create_effect(cx, {
+                let on_drag_end = on_drag_end.clone();
+                move || {
+                    if let Some(e) = drag_ended.get().as_ref() {
+                        let id = e
+                            .data_transfer()
+                            .and_then(|transfer| transfer_drag_id(&transfer));
+                        if id.is_some() && id == drag_id.get() {
+                            untrack(|| on_drag_end(e));
+                        }
+                    }
+                }
+            });
+
+            // With a handle, the element is only made draggable while the pointer is down on the
+            // handle, so the rest of the element keeps its normal behaviour.
+            let on_pointer_down = {
+                let node = node.clone();
+                move |e: PointerEvent| {
+                    let is_on_handle = options.is_on_handle(&node, &e);
+                    on_handle.set(is_on_handle);
+                    if backend == DragBackend::Native
+                        && options.handle.is_some()
+                        && !options.is_disabled()
+                    {
+                        if is_on_handle {
+                            node.set_attribute("draggable".into(), "true".into());
+                        } else {
+                            node.remove_attribute("draggable".into());
+                        }
+                    }
                 }
             };
 
-            node.set_attribute("draggable".into(), "true".into());
+            if backend == DragBackend::Pointer {
+                if G::IS_BROWSER {
+                    // Without this, touch browsers scroll the page instead of sending pointer
+                    // events.
+                    for element in options.handle_elements(&node) {
+                        element.style().set_property("touch-action", "none").ok();
+                    }
+                }
+                create_pointer_drag(cx, &node, drag_image, {
+                    let node = node.clone();
+                    move |e: &PointerEvent| !options.is_disabled() && options.is_on_handle(&node, e)
+                });
+            }
+            node.event(cx, ev::pointerdown, on_pointer_down);
             node.event(cx, ev::dragstart, on_drag_start);
-            node.event(cx, ev::dragend, on_drag_end);
+            node.event(cx, ev::drag, on_drag);
+            node.event(cx, ev::dragend, move |e: DragEvent| on_drag_end(&e));
+        }
+    });
+
+    create_effect(cx, move || {
+        let disabled = options
+            .disabled
+            .map(|disabled| *disabled.get())
+            .unwrap_or(false);
+        if let Some(node) = node_ref.try_get_raw() {
+            if disabled {
+                node.remove_attribute("draggable".into());
+                node.remove_class(&options.dragging_class);
+                node.remove_attribute("data-dragging".into());
+                node.set_attribute("aria-disabled".into(), "true".into());
+                set_if_changed(options.is_dragging, false);
+            } else {
+                if backend == DragBackend::Native && options.handle.is_none() {
+                    node.set_attribute("draggable".into(), "true".into());
+                }
+                if options.disabled.is_some() {
+                    node.remove_attribute("aria-disabled".into());
+                }
+            }
         }
     });
 }
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn drag_view_is_replaced_on_each_drag() {
+        let view = |cx: Scope<'_>| view! { cx, span { "Dragging" } };
+        let container = create_drag_view_container();
+        let scope = RefCell::new(None);
+
+        render_drag_view(&view, &container, &scope);
+        render_drag_view(&view, &container, &scope);
+        assert_eq!(container.child_element_count(), 1);
+
+        dispose_drag_view(&scope, Some(&container));
+        assert_eq!(container.child_element_count(), 0);
+    }
+}
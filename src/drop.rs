@@ -1,42 +1,337 @@
-use crate::FromTransfer;
-use sycamore::{prelude::*, web::html::ev};
-use web_sys::DragEvent;
+use crate::{
+    files::FileFilter, set_if_changed, transfer_types, use_dnd_context, DndContext, DropPosition,
+    DroppedFiles, FromTransfer, Orientation, PlacementThresholds,
+};
+use std::{cell::Cell, fmt::Display, future::Future};
+use sycamore::{futures::spawn_local_scoped, prelude::*, web::html::ev};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{DataTransfer, DragEvent, Event};
+
+/// The property set on a drag event once a droppable took it, so droppables around it that the
+/// event bubbles to leave it alone.
+const CLAIMED_PROPERTY: &str = "__sycamoreDndClaimed";
+
+/// Whether a droppable nested in the current one already took the event.
+fn is_claimed(e: &Event) -> bool {
+    js_sys::Reflect::get(e, &CLAIMED_PROPERTY.into())
+        .map(|claimed| claimed.is_truthy())
+        .unwrap_or(false)
+}
+
+/// Mark the event as taken by the current droppable.
+fn claim(e: &Event) {
+    js_sys::Reflect::set(e, &CLAIMED_PROPERTY.into(), &true.into()).unwrap();
+}
+
+/// The return value of an `on_drop` callback. Returning an error rejects the dropped item.
+///
+/// This is implemented for `()`, for callbacks that always succeed, and for `Result<(), E>`.
+pub trait IntoDropResult {
+    /// Convert the value to a result, with the error formatted as a string.
+    fn into_drop_result(self) -> Result<(), String>;
+}
+
+impl IntoDropResult for () {
+    fn into_drop_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Display> IntoDropResult for Result<(), E> {
+    fn into_drop_result(self) -> Result<(), String> {
+        self.map_err(|err| err.to_string())
+    }
+}
+
+/// An extra drop handler registered with `on_drop_typed`, `on_files` or `on_text`.
+struct DropHandler<'cx> {
+    /// Whether the item looks like it can be handled, used while hovering
+    #[allow(clippy::type_complexity)]
+    matches: Box<dyn Fn(&DndContext, &DataTransfer, Option<&FileFilter>) -> bool + 'cx>,
+    /// Handle the dropped item, or return `None` if it can't be read
+    #[allow(clippy::type_complexity)]
+    drop: Box<
+        dyn Fn(&DndContext, &DataTransfer, Option<&FileFilter>) -> Option<Result<(), String>> + 'cx,
+    >,
+}
 
 /// The builder for the [`create_droppable`] options
 pub struct DroppableBuilder<'cx, G: Html, T: FromTransfer + 'static = ()> {
     scope: Scope<'cx>,
-    on_drop: Option<Box<dyn Fn(T) + 'cx>>,
+    #[allow(clippy::type_complexity)]
+    on_drop: Option<Box<dyn Fn(T, DropPosition) -> Result<(), String> + 'cx>>,
     #[allow(clippy::type_complexity)]
     accept: Option<Box<dyn Fn(&T) -> bool + 'cx>>,
+    handlers: Vec<DropHandler<'cx>>,
+    pub(crate) file_filter: Option<FileFilter>,
     hovering_class: String,
+    drop_target_class: String,
+    is_drop_target: &'cx Signal<bool>,
     node_ref: Option<&'cx NodeRef<G>>,
+    disabled: Option<&'cx ReadSignal<bool>>,
+    is_over: &'cx Signal<bool>,
+    can_drop: &'cx Signal<bool>,
+    on_drag_enter: Option<Box<dyn Fn() + 'cx>>,
+    on_drag_leave: Option<Box<dyn Fn() + 'cx>>,
+    orientation: Orientation,
+    thresholds: PlacementThresholds,
+    hover_position: &'cx Signal<Option<DropPosition>>,
+    pending_count: &'cx Signal<u32>,
+    pending: &'cx ReadSignal<bool>,
+    pending_class: String,
+    block_while_pending: bool,
+    propagate: bool,
+    rejected_class: String,
+    rejected_duration: i32,
 }
 
 impl<'cx, G: Html, T: FromTransfer + 'static> DroppableBuilder<'cx, G, T> {
     fn new(scope: Scope<'cx>) -> Self {
+        let pending_count = create_signal(scope, 0);
         Self {
             scope,
             on_drop: None,
             accept: None,
+            handlers: Vec::new(),
+            file_filter: None,
             hovering_class: Default::default(),
+            drop_target_class: Default::default(),
+            is_drop_target: create_signal(scope, false),
             node_ref: None,
+            disabled: None,
+            is_over: create_signal(scope, false),
+            can_drop: create_signal(scope, false),
+            on_drag_enter: None,
+            on_drag_leave: None,
+            orientation: Default::default(),
+            thresholds: Default::default(),
+            hover_position: create_signal(scope, None),
+            pending_count,
+            pending: create_selector(scope, move || *pending_count.get() > 0),
+            pending_class: Default::default(),
+            block_while_pending: false,
+            propagate: false,
+            rejected_class: Default::default(),
+            rejected_duration: 500,
         }
     }
 
     /// Sets a callback to run when an item is dropped on this droppable element.
     /// The argument is parsed from the item's [`DataTransfer`](web_sys::DataTransfer).
-    pub fn on_drop(mut self, f: impl Fn(T) + 'cx) -> Self {
-        self.on_drop = Some(Box::new(f));
+    ///
+    /// The callback can return a `Result` to validate the item after it was dropped. If it
+    /// returns an error, the item is rejected: the `dropEffect` is set to `none` so the browser
+    /// animates it back, the `rejected_class` is set for a moment and the error is passed to the
+    /// draggable's `on_drag_end` callback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn Inbox<G: Html>(cx: Scope) -> View<G> {
+    ///     let drop = create_droppable(cx)
+    ///         .on_drop(|subject: String| {
+    ///             if subject.is_empty() {
+    ///                 return Err("Messages need a subject");
+    ///             }
+    ///             log::info!("Moved {subject} to the inbox");
+    ///             Ok(())
+    ///         })
+    ///         .rejected_class("shake")
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "inbox", ref = drop) { "Inbox" }
+    ///     }
+    /// }
+    /// ```
+    pub fn on_drop<R: IntoDropResult>(mut self, f: impl Fn(T) -> R + 'cx) -> Self {
+        self.on_drop = Some(Box::new(move |data, _| f(data).into_drop_result()));
+        self
+    }
+
+    /// Like `on_drop`, but also receives the [`DropPosition`] inside the element. Use
+    /// `orientation` and `placement_thresholds` to configure how the
+    /// [`Placement`](crate::Placement) is calculated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn ListItem<G: Html>(cx: Scope) -> View<G> {
+    ///     let drop = create_droppable(cx)
+    ///         .on_drop_at(|id: u32, position| match position.placement {
+    ///             Placement::Before => log::info!("Insert {id} before this item"),
+    ///             Placement::After => log::info!("Insert {id} after this item"),
+    ///             Placement::Inside => log::info!("Move {id} into this item"),
+    ///         })
+    ///         .placement_thresholds(0.25, 0.75)
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         li(ref = drop) { "Drop here" }
+    ///     }
+    /// }
+    /// ```
+    pub fn on_drop_at<R: IntoDropResult>(mut self, f: impl Fn(T, DropPosition) -> R + 'cx) -> Self {
+        self.on_drop = Some(Box::new(move |data, position| {
+            f(data, position).into_drop_result()
+        }));
+        self
+    }
+
+    /// Like `on_drop`, but runs an async callback. The future is spawned in the droppable's scope,
+    /// and the `pending` signal and `pending_class` are set until it completes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// # async fn save(name: String) {}
+    /// #[component]
+    /// fn DropZone<G: Html>(cx: Scope) -> View<G> {
+    ///     let droppable = create_droppable(cx)
+    ///         .on_drop_async(|name: String| async move { save(name).await })
+    ///         .pending_class("saving")
+    ///         .block_while_pending(true);
+    ///     let pending = droppable.pending();
+    ///     let drop = droppable.build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "drop-zone", ref = drop) {
+    ///             (if *pending.get() { "Saving..." } else { "Drop here" })
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn on_drop_async<F: Future<Output = ()> + 'cx>(self, f: impl Fn(T) -> F + 'cx) -> Self {
+        let (scope, pending_count) = (self.scope, self.pending_count);
+        self.on_drop(move |data| {
+            let future = f(data);
+            pending_count.set(*pending_count.get_untracked() + 1);
+            spawn_local_scoped(scope, async move {
+                future.await;
+                pending_count.set(*pending_count.get_untracked() - 1);
+            });
+        })
+    }
+
+    /// A signal that is `true` while a callback set with `on_drop_async` is running.
+    pub fn pending(&self) -> &'cx ReadSignal<bool> {
+        self.pending
+    }
+
+    /// A class or list of classes to set while a callback set with `on_drop_async` is running.
+    pub fn pending_class(mut self, class: impl Into<String>) -> Self {
+        self.pending_class = class.into();
+        self
+    }
+
+    /// Whether to reject items while a callback set with `on_drop_async` is running. Defaults to
+    /// `false`.
+    pub fn block_while_pending(mut self, block: bool) -> Self {
+        self.block_while_pending = block;
+        self
+    }
+
+    /// A class or list of classes to set for a moment when the `on_drop` callback rejects an item.
+    pub fn rejected_class(mut self, class: impl Into<String>) -> Self {
+        self.rejected_class = class.into();
+        self
+    }
+
+    /// How long the `rejected_class` stays on the element. Defaults to 500ms.
+    pub fn rejected_duration(mut self, millis: u32) -> Self {
+        self.rejected_duration = millis as i32;
+        self
+    }
+
+    /// Whether droppables around this one also receive the items it accepts. Defaults to `false`.
+    ///
+    /// When droppables are nested, the innermost one that accepts an item wins: only it shows
+    /// the `hovering_class` and receives the drop, while the ones around it act like the item
+    /// isn't over them. If the innermost one doesn't accept the item, the next one out gets the
+    /// chance. With `propagate` set, this droppable handles the item and still lets the ones
+    /// around it handle it too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn FolderList<G: Html>(cx: Scope) -> View<G> {
+    ///     let list = create_droppable(cx)
+    ///         .on_drop(|file: String| log::info!("Moved {file} to the root folder"))
+    ///         .hovering_class("drop-here")
+    ///         .build();
+    ///     let folder = create_droppable(cx)
+    ///         .on_drop(|file: String| log::info!("Moved {file} to the documents folder"))
+    ///         .hovering_class("drop-here")
+    ///         .build();
+    ///     let recent = create_droppable(cx)
+    ///         .on_drop(|file: String| log::info!("Added {file} to the recent files"))
+    ///         .propagate(true)
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         ul(ref = list) {
+    ///             li(ref = folder) { "Documents" }
+    ///             li(ref = recent) { "Recent" }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn propagate(mut self, propagate: bool) -> Self {
+        self.propagate = propagate;
+        self
+    }
+
+    /// Sets the axis used to decide if an item is placed before or after this element. Defaults
+    /// to [`Orientation::Vertical`].
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the fractions of the element along the `orientation` axis that count as dropping
+    /// before or after it, with everything in between counting as inside. For example, `0.25` and
+    /// `0.75` make the middle half of the element drop inside it. Defaults to `0.5` for both, so
+    /// items are never dropped inside. Both are clamped between `0.0` and `1.0`, and `after` to be
+    /// at least `before`.
+    pub fn placement_thresholds(mut self, before: f64, after: f64) -> Self {
+        self.thresholds = PlacementThresholds::new(before, after);
         self
     }
 
     /// A callback to check if the incoming [`DataTransfer`](web_sys::DataTransfer) should be accepted.
     /// The argument is parsed from the item's [`DataTransfer`](web_sys::DataTransfer).
+    ///
+    /// Browsers only allow reading the data once it's dropped, so while hovering, items are accepted
+    /// based on their type (see [`FromTransfer::matches_transfer`]) and this callback runs on drop.
     pub fn accept(mut self, f: impl Fn(&T) -> bool + 'cx) -> Self {
         self.accept = Some(Box::new(f));
         self
     }
 
+    /// Sets a callback to run when an item is dragged into the element. Moving between children of
+    /// the element doesn't count as leaving and entering again.
+    pub fn on_drag_enter(mut self, f: impl Fn() + 'cx) -> Self {
+        self.on_drag_enter = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback to run when an item is dragged out of the element, or dropped on it.
+    pub fn on_drag_leave(mut self, f: impl Fn() + 'cx) -> Self {
+        self.on_drag_leave = Some(Box::new(f));
+        self
+    }
+
     /// A class or list of classes to set when a valid item is hovering over the element.
     /// They are automatically removed when the item leaves or is dropped.
     pub fn hovering_class(mut self, class: impl Into<String>) -> Self {
@@ -44,6 +339,42 @@ impl<'cx, G: Html, T: FromTransfer + 'static> DroppableBuilder<'cx, G, T> {
         self
     }
 
+    /// A class or list of classes to set from the start to the end of a drag, if the dragged item
+    /// would be accepted. Unlike `hovering_class`, this is set on every matching droppable at
+    /// once, to show the user where the item can go.
+    ///
+    /// Only drags started by draggables in the same [`DndContext`] are known before they
+    /// hover the element, so items dragged in from outside the page aren't highlighted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn Column<G: Html>(cx: Scope) -> View<G> {
+    ///     let drop = create_droppable(cx)
+    ///         .on_drop(|task: u32| log::info!("Moved task {task} here"))
+    ///         .drop_target_class("can-drop")
+    ///         .hovering_class("drop-here")
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "column", ref = drop)
+    ///     }
+    /// }
+    /// ```
+    pub fn drop_target_class(mut self, class: impl Into<String>) -> Self {
+        self.drop_target_class = class.into();
+        self
+    }
+
+    /// A signal that is `true` while an item that would be accepted is being dragged anywhere on
+    /// the page. See `drop_target_class`.
+    pub fn is_drop_target(&self) -> &'cx ReadSignal<bool> {
+        self.is_drop_target
+    }
+
     /// An existing [`NodeRef`] to use instead of creating a new one. Useful for combining drag and
     /// drop on one element, or using your own logic that requires [`NodeRef`].
     pub fn node_ref(mut self, node_ref: &'cx NodeRef<G>) -> Self {
@@ -51,6 +382,182 @@ impl<'cx, G: Html, T: FromTransfer + 'static> DroppableBuilder<'cx, G, T> {
         self
     }
 
+    /// Disable the drop zone while the signal is `true`. A disabled droppable ignores all items
+    /// and has `aria-disabled` set.
+    pub fn disabled(mut self, disabled: &'cx ReadSignal<bool>) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// A signal that is `true` while an item is dragged over the element, whether it would be
+    /// accepted or not.
+    pub fn is_over(&self) -> &'cx ReadSignal<bool> {
+        self.is_over
+    }
+
+    /// A signal that is `true` while an item that would be accepted is dragged over the element.
+    /// Useful for showing content like "Release to drop".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn DropZone<G: Html>(cx: Scope) -> View<G> {
+    ///     let droppable = create_droppable(cx).on_drop(|name: String| log::info!("{name}"));
+    ///     let can_drop = droppable.can_drop();
+    ///     let drop = droppable.build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "drop-zone", ref = drop) {
+    ///             (if *can_drop.get() { "Release to drop" } else { "Drop here" })
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn can_drop(&self) -> &'cx ReadSignal<bool> {
+        self.can_drop
+    }
+
+    /// Set the `rejected_class` and remove it again after `rejected_duration`. A rejection while the
+    /// class is still set restarts the timer.
+    fn flash_rejected(&self, node: &G, timeout: &Cell<Option<i32>>) {
+        if self.rejected_class.is_empty() {
+            return;
+        }
+        let window = web_sys::window().unwrap();
+        if let Some(handle) = timeout.take() {
+            window.clear_timeout_with_handle(handle);
+        }
+        node.add_class(&self.rejected_class);
+
+        let remove = {
+            let (node, class) = (node.clone(), self.rejected_class.clone());
+            Closure::once_into_js(move || node.remove_class(&class))
+        };
+        let handle = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                remove.unchecked_ref(),
+                self.rejected_duration,
+            )
+            .unwrap();
+        timeout.set(Some(handle));
+    }
+
+    /// Update the hover state while an item is over the element.
+    fn set_hovering(&self, node: &G, accepted: bool) {
+        if accepted {
+            node.add_class(&self.hovering_class);
+        } else {
+            node.remove_class(&self.hovering_class);
+        }
+        set_if_changed(self.is_over, true);
+        set_if_changed(self.can_drop, accepted);
+    }
+
+    /// Reset the hover state once an item left the element.
+    fn clear_hovering(&self, node: &G) {
+        node.remove_class(&self.hovering_class);
+        set_if_changed(self.hover_position, None);
+        set_if_changed(self.is_over, false);
+        set_if_changed(self.can_drop, false);
+    }
+
+    /// A signal with the position of the item currently dragged over the element, or `None` if
+    /// there is none.
+    pub fn hover_position(&self) -> &'cx ReadSignal<Option<DropPosition>> {
+        self.hover_position
+    }
+
+    /// Calculate the position of a drag event inside the element.
+    fn position(&self, node: &G, e: &DragEvent) -> DropPosition {
+        DropPosition::from_event(
+            e,
+            &node.to_web_sys().unchecked_into(),
+            self.orientation,
+            self.thresholds,
+        )
+    }
+
+    /// Whether the droppable currently ignores all items, because it's disabled or blocked by a
+    /// pending drop.
+    fn is_disabled(&self) -> bool {
+        self.disabled
+            .map(|disabled| *disabled.get_untracked())
+            .unwrap_or(false)
+            || (self.block_while_pending && *self.pending.get_untracked())
+    }
+
+    /// Check whether a hovering item should be accepted. Browsers hide the data until it's dropped,
+    /// so unless the value is held in memory by the [`DndContext`], only the types are checked and
+    /// `accept` runs on drop.
+    fn accepts_hovering(&self, context: &DndContext, transfer: &DataTransfer) -> bool {
+        let uses_own_type = self.on_drop.is_some() || self.handlers.is_empty();
+        (uses_own_type && self.accepts_own_type(context, transfer))
+            || self
+                .handlers
+                .iter()
+                .any(|handler| (handler.matches)(context, transfer, self.file_filter.as_ref()))
+    }
+
+    /// Check whether a hovering item can be read as `T` and passes `accept`.
+    fn accepts_own_type(&self, context: &DndContext, transfer: &DataTransfer) -> bool {
+        if let Some(data) = context.value::<T>(transfer) {
+            return self
+                .accept
+                .as_ref()
+                .map(|accept| accept(&data))
+                .unwrap_or(true);
+        }
+        if !T::matches_transfer(transfer) {
+            return false;
+        }
+        if let Some(filter) = self.file_filter.as_ref() {
+            if !filter.matches_hovering_as::<T>(transfer) {
+                return false;
+            }
+        }
+        self.accept
+            .as_ref()
+            .map(|accept| {
+                T::from_transfer(transfer)
+                    .map(|data| accept(&data))
+                    .unwrap_or(true)
+            })
+            .unwrap_or(true)
+    }
+
+    /// Pass a dropped item to `on_drop`, or the first extra handler that can read it. Returns
+    /// `None` if no handler took it.
+    fn handle_drop(
+        &self,
+        context: &DndContext,
+        transfer: &DataTransfer,
+        position: DropPosition,
+    ) -> Option<Result<(), String>> {
+        if let Some(on_drop) = self.on_drop.as_ref() {
+            let data = context.read::<T>(transfer).filter(|data| {
+                self.accept
+                    .as_ref()
+                    .map(|accept| accept(data))
+                    .unwrap_or(true)
+            });
+            if let Some(data) = data {
+                if let Some(filter) = self.file_filter.as_ref() {
+                    if let Err(err) = filter.check_dropped(&data) {
+                        return Some(Err(err));
+                    }
+                }
+                log::trace!("Data found and accepted, calling `on_drop`");
+                return Some(on_drop(data, position));
+            }
+        }
+        self.handlers
+            .iter()
+            .find_map(|handler| (handler.drop)(context, transfer, self.file_filter.as_ref()))
+    }
+
     /// Create the droppable logic. Returns a [`NodeRef`] that needs to be set as the element's `ref`
     /// attribute.
     pub fn build(self) -> &'cx NodeRef<G> {
@@ -60,6 +567,92 @@ impl<'cx, G: Html, T: FromTransfer + 'static> DroppableBuilder<'cx, G, T> {
     }
 }
 
+/// Droppables that dispatch items to several handlers by their type.
+impl<'cx, G: Html> DroppableBuilder<'cx, G> {
+    /// Adds a handler for items of type `U`, so one drop zone can accept several kinds of items.
+    /// Handlers added with `on_drop_typed`, `on_files` and `on_text` are tried in the order they
+    /// were added, and the first one that can read the item handles it. While hovering, the item
+    /// is accepted if any of them matches it.
+    ///
+    /// Like `on_drop`, the callback can return a `Result` to reject the item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use serde::Deserialize;
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[derive(Deserialize)]
+    /// struct Card {
+    ///     title: String,
+    /// }
+    ///
+    /// #[component]
+    /// fn Board<G: Html>(cx: Scope) -> View<G> {
+    ///     let drop = create_droppable(cx)
+    ///         .on_drop_typed(|card: Card| log::info!("Moved {}", card.title))
+    ///         .on_files(|files| log::info!("Attached {} files", files.len()))
+    ///         .on_text(|text| log::info!("Created a card from {text}"))
+    ///         .build();
+    ///
+    ///     view! { cx,
+    ///         div(class = "board", ref = drop) { "Drop cards, files or text here" }
+    ///     }
+    /// }
+    /// ```
+    pub fn on_drop_typed<U: FromTransfer + 'static, R: IntoDropResult>(
+        mut self,
+        f: impl Fn(U) -> R + 'cx,
+    ) -> Self {
+        self.handlers.push(DropHandler {
+            matches: Box::new(|context, transfer, filter| {
+                let passes_filter = filter
+                    .map(|filter| filter.matches_hovering_as::<U>(transfer))
+                    .unwrap_or(true);
+                (U::matches_transfer(transfer) && passes_filter)
+                    || context.value::<U>(transfer).is_some()
+            }),
+            drop: Box::new(move |context, transfer, filter| {
+                let data = context.read::<U>(transfer)?;
+                if let Some(Err(err)) = filter.map(|filter| filter.check_dropped(&data)) {
+                    return Some(Err(err));
+                }
+                Some(f(data).into_drop_result())
+            }),
+        });
+        self
+    }
+
+    /// Adds a handler for files dragged in from outside the browser. See `on_drop_typed` for how
+    /// handlers are chosen. File filters like [`accept_mime`](Self::accept_mime) and
+    /// [`max_size`](Self::max_size) apply to these files too.
+    pub fn on_files<R: IntoDropResult>(self, f: impl Fn(DroppedFiles) -> R + 'cx) -> Self {
+        self.on_drop_typed(f)
+    }
+
+    /// Adds a handler for plain text (`text/plain`), like text selected in another application.
+    /// See `on_drop_typed` for how handlers are chosen.
+    pub fn on_text<R: IntoDropResult>(mut self, f: impl Fn(String) -> R + 'cx) -> Self {
+        self.handlers.push(DropHandler {
+            matches: Box::new(|_, transfer, _| {
+                transfer_types(transfer).iter().any(|ty| ty == "text/plain")
+            }),
+            drop: Box::new(move |_, transfer, _| {
+                // `get_data` returns an empty string for missing types, so check the types first.
+                if !transfer_types(transfer).iter().any(|ty| ty == "text/plain") {
+                    return None;
+                }
+                let text = transfer
+                    .get_data("text/plain")
+                    .ok()
+                    .filter(|text| !text.is_empty())?;
+                Some(f(text).into_drop_result())
+            }),
+        });
+        self
+    }
+}
+
 /// Create a drop zone for an element. The [`DroppableBuilder`] can be used to further configure the
 /// drop zone.
 ///
@@ -95,28 +688,35 @@ fn create_droppable_effect<'cx, G: Html, T: FromTransfer + 'static>(
     // SAFETY: This is safe as long as the builder has no custom `Drop` implementation
     // See documentation for `create_ref_unsafe`.
     let options = unsafe { create_ref_unsafe(cx, options) };
+    let context = create_ref(cx, use_dnd_context(cx));
+    let depth = create_ref(cx, Cell::new(0u32));
+    let rejected_timeout = create_ref(cx, Cell::new(None));
 
     create_effect(cx, move || {
         if let Some(node) = node_ref.try_get_raw() {
+            // `dragenter` and `dragleave` also fire when moving between children, so count how
+            // deep the item is to only react when it actually enters or leaves the element.
             let on_drag_enter = {
                 let node = node.clone();
                 move |e: DragEvent| {
-                    log::trace!("Drag enter");
+                    if options.is_disabled() {
+                        return;
+                    }
                     e.prevent_default();
+                    depth.set(depth.get() + 1);
+                    if depth.get() > 1 {
+                        return;
+                    }
+                    log::trace!("Drag enter");
 
-                    let should_accept = options
-                        .accept
-                        .as_ref()
-                        .map(|accept| {
-                            if let Some(data) = T::from_transfer(&e.data_transfer().unwrap()) {
-                                accept(&data)
-                            } else {
-                                false
-                            }
-                        })
-                        .unwrap_or(true);
-                    if should_accept {
-                        node.add_class(&options.hovering_class);
+                    let accepted = !is_claimed(&e)
+                        && options.accepts_hovering(context, &e.data_transfer().unwrap());
+                    if accepted && !options.propagate {
+                        claim(&e);
+                    }
+                    options.set_hovering(&node, accepted);
+                    if let Some(on_drag_enter) = options.on_drag_enter.as_ref() {
+                        on_drag_enter();
                     }
                 }
             };
@@ -124,50 +724,90 @@ fn create_droppable_effect<'cx, G: Html, T: FromTransfer + 'static>(
             let on_drag_leave = {
                 let node = node.clone();
                 move |e: DragEvent| {
+                    if depth.get() == 0 {
+                        return;
+                    }
                     e.prevent_default();
-
-                    node.remove_class(&options.hovering_class);
+                    depth.set(depth.get() - 1);
+                    if depth.get() > 0 {
+                        return;
+                    }
                     log::trace!("Drag leave");
+
+                    options.clear_hovering(&node);
+                    if let Some(on_drag_leave) = options.on_drag_leave.as_ref() {
+                        on_drag_leave();
+                    }
                 }
             };
 
-            let on_drag_over = |e: DragEvent| {
-                let should_accept = if let Some(accept) = options.accept.as_ref() {
-                    if let Some(data) = T::from_transfer(&e.data_transfer().unwrap()) {
-                        accept(&data)
-                    } else {
-                        false
+            let on_drag_over = {
+                let node = node.clone();
+                move |e: DragEvent| {
+                    if options.is_disabled() {
+                        return;
                     }
-                } else {
-                    true
-                };
-                if should_accept {
-                    e.prevent_default();
+                    if is_claimed(&e) {
+                        // A nested droppable took the item.
+                        options.set_hovering(&node, false);
+                        set_if_changed(options.hover_position, None);
+                        return;
+                    }
+                    let accepted = options.accepts_hovering(context, &e.data_transfer().unwrap());
+                    if accepted {
+                        e.prevent_default();
+                        if !options.propagate {
+                            claim(&e);
+                        }
+                    }
+                    options.set_hovering(&node, accepted);
+                    set_if_changed(options.hover_position, Some(options.position(&node, &e)));
                 }
             };
 
             let on_drop = {
                 let node = node.clone();
                 move |e: DragEvent| {
+                    if options.is_disabled() {
+                        return;
+                    }
                     log::trace!("Dropping");
-                    node.remove_class(&options.hovering_class);
-
-                    if let Some((on_drop, data)) = options
-                        .on_drop
-                        .as_ref()
-                        .zip(T::from_transfer(&e.data_transfer().unwrap()))
-                    {
-                        if options
-                            .accept
-                            .as_ref()
-                            .map(|accept| accept(&data))
-                            .unwrap_or(true)
+                    options.clear_hovering(&node);
+                    // Only report leaving once the drop was handled, so `on_drag_leave` can reset
+                    // state the drop still needs.
+                    let entered = depth.replace(0) > 0;
+                    let leave = || {
+                        if let Some(on_drag_leave) =
+                            options.on_drag_leave.as_ref().filter(|_| entered)
                         {
-                            log::trace!("Data found and accepted, calling `on_drop`");
-                            e.prevent_default();
-                            on_drop(data);
+                            on_drag_leave();
+                        }
+                    };
+
+                    if is_claimed(&e) {
+                        log::trace!("Dropped on a nested droppable");
+                        leave();
+                        return;
+                    }
+                    let transfer = e.data_transfer().unwrap();
+                    let position = options.position(&node, &e);
+                    if let Some(result) = options.handle_drop(context, &transfer, position) {
+                        e.prevent_default();
+                        if !options.propagate {
+                            claim(&e);
+                        }
+                        if let Err(err) = result.as_ref() {
+                            log::trace!("Drop rejected: {err}");
+                            transfer.set_drop_effect("none");
+                            options.flash_rejected(&node, rejected_timeout);
                         }
+                        context.set_drop(
+                            &transfer,
+                            node.to_web_sys().unchecked_into(),
+                            result.err(),
+                        );
                     }
+                    leave();
                 }
             };
 
@@ -177,4 +817,67 @@ fn create_droppable_effect<'cx, G: Html, T: FromTransfer + 'static>(
             node.event(cx, ev::drop, on_drop);
         }
     });
+
+    create_effect(cx, move || {
+        let transfer = context.active_transfer();
+        let node = node_ref.try_get_raw();
+        let accepted = untrack(|| {
+            node.is_some()
+                && !options.is_disabled()
+                && transfer
+                    .map(|transfer| options.accepts_hovering(context, &transfer))
+                    .unwrap_or(false)
+        });
+        set_if_changed(options.is_drop_target, accepted);
+        if let Some(node) = node {
+            if accepted {
+                node.add_class(&options.drop_target_class);
+            } else {
+                node.remove_class(&options.drop_target_class);
+            }
+        }
+    });
+
+    // Browsers don't send `dragleave` for children that are removed while the item is over them,
+    // so the count can get stuck. Reset it once a drag in the app ended.
+    create_effect(cx, move || {
+        if context.active_transfer().is_none() && depth.replace(0) > 0 {
+            untrack(|| {
+                if let Some(node) = node_ref.try_get_raw() {
+                    options.clear_hovering(&node);
+                }
+                if let Some(on_drag_leave) = options.on_drag_leave.as_ref() {
+                    on_drag_leave();
+                }
+            });
+        }
+    });
+
+    if !options.pending_class.is_empty() {
+        create_effect(cx, move || {
+            let pending = *options.pending.get();
+            if let Some(node) = node_ref.try_get_raw() {
+                if pending {
+                    node.add_class(&options.pending_class);
+                } else {
+                    node.remove_class(&options.pending_class);
+                }
+            }
+        });
+    }
+
+    if let Some(disabled) = options.disabled {
+        create_effect(cx, move || {
+            let disabled = *disabled.get();
+            if let Some(node) = node_ref.try_get_raw() {
+                if disabled {
+                    depth.set(0);
+                    options.clear_hovering(&node);
+                    node.set_attribute("aria-disabled".into(), "true".into());
+                } else {
+                    node.remove_attribute("aria-disabled".into());
+                }
+            }
+        });
+    }
 }
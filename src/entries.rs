@@ -0,0 +1,184 @@
+use crate::{transfer_types, DroppedFile, FromTransfer};
+use js_sys::{Array, Promise};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    DataTransfer, File, FileSystemDirectoryEntry, FileSystemDirectoryReader, FileSystemEntry,
+    FileSystemFileEntry,
+};
+
+/// A file found in a dropped folder, or dropped directly.
+#[derive(Clone, Debug)]
+pub struct DroppedEntry {
+    /// The path of the file relative to the dropped items, like `project/src/main.rs` for a file
+    /// inside a dropped `project` folder.
+    pub path: String,
+    /// The file itself
+    pub file: DroppedFile,
+}
+
+/// An item dropped from outside the browser.
+#[derive(Clone, Debug)]
+enum Root {
+    /// A file or folder
+    Entry(FileSystemEntry),
+    /// A file from a browser that doesn't expose file system entries
+    File(File),
+}
+
+/// The files and folders dropped on a droppable. Unlike [`DroppedFiles`](crate::DroppedFiles),
+/// this includes the contents of dropped folders, which are read asynchronously with
+/// [`DroppedEntries::files`].
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[component]
+/// fn Import<G: Html>(cx: Scope) -> View<G> {
+///     let drop = create_droppable(cx)
+///         .on_drop_async(|entries: DroppedEntries| async move {
+///             let files = entries.max_depth(8).max_count(1000).files().await.unwrap();
+///             for entry in files {
+///                 log::info!("Importing {} ({} bytes)", entry.path, entry.file.size());
+///             }
+///         })
+///         .build();
+///
+///     view! { cx,
+///         div(class = "import", ref = drop) { "Drop a project folder here" }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DroppedEntries {
+    roots: Vec<Root>,
+    max_depth: Option<usize>,
+    max_count: Option<usize>,
+}
+
+impl DroppedEntries {
+    /// The names of the dropped files and folders, without their contents.
+    pub fn names(&self) -> Vec<String> {
+        self.roots
+            .iter()
+            .map(|root| match root {
+                Root::Entry(entry) => entry.name(),
+                Root::File(file) => file.name(),
+            })
+            .collect()
+    }
+
+    /// Only read files up to this many folders deep. `0` only includes the dropped files
+    /// themselves, `1` also the files directly inside dropped folders, and so on.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Stop reading once this many files were found.
+    pub fn max_count(mut self, count: usize) -> Self {
+        self.max_count = Some(count);
+        self
+    }
+
+    /// Walk the dropped folders and read all files in them, in the order they are listed by the
+    /// browser.
+    pub async fn files(&self) -> Result<Vec<DroppedEntry>, JsValue> {
+        let mut files = Vec::new();
+        let mut stack = Vec::new();
+        for root in self.roots.iter().rev() {
+            match root {
+                Root::Entry(entry) => stack.push((entry.clone(), entry.name(), 0)),
+                Root::File(file) => files.push(DroppedEntry {
+                    path: file.name(),
+                    file: DroppedFile::new(file.clone()),
+                }),
+            }
+        }
+
+        while let Some((entry, path, depth)) = stack.pop() {
+            if self
+                .max_count
+                .map(|max| files.len() >= max)
+                .unwrap_or(false)
+            {
+                break;
+            }
+            if entry.is_file() {
+                let file = read_file(entry.unchecked_ref()).await?;
+                files.push(DroppedEntry {
+                    path,
+                    file: DroppedFile::new(file),
+                });
+                continue;
+            }
+            let can_descend = self.max_depth.map(|max| depth < max).unwrap_or(true);
+            if entry.is_directory() && can_descend {
+                let reader = entry
+                    .unchecked_ref::<FileSystemDirectoryEntry>()
+                    .create_reader();
+                let children = read_entries(&reader).await?;
+                for child in children.into_iter().rev() {
+                    let child_path = format!("{path}/{}", child.name());
+                    stack.push((child, child_path, depth + 1));
+                }
+            }
+        }
+        if let Some(max) = self.max_count {
+            files.truncate(max);
+        }
+        Ok(files)
+    }
+}
+
+impl FromTransfer for DroppedEntries {
+    fn from_transfer(transfer: &DataTransfer) -> Option<Self> {
+        // Entries can only be taken from the items during the `drop` event, but can be read later.
+        let items = transfer.items();
+        let roots = (0..items.length())
+            .filter_map(|i| items.get(i))
+            .filter(|item| item.kind() == "file")
+            .filter_map(|item| match item.webkit_get_as_entry().ok().flatten() {
+                Some(entry) => Some(Root::Entry(entry)),
+                None => item.get_as_file().ok().flatten().map(Root::File),
+            })
+            .collect::<Vec<_>>();
+        (!roots.is_empty()).then_some(DroppedEntries {
+            roots,
+            max_depth: None,
+            max_count: None,
+        })
+    }
+
+    fn matches_transfer(transfer: &DataTransfer) -> bool {
+        transfer_types(transfer).iter().any(|ty| ty == "Files")
+    }
+}
+
+/// Read the [`File`] of a file entry.
+async fn read_file(entry: &FileSystemFileEntry) -> Result<File, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        entry.file_with_callback_and_callback(&resolve, &reject);
+    });
+    Ok(JsFuture::from(promise).await?.unchecked_into())
+}
+
+/// Read all entries of a folder. Browsers return them in batches, so this reads until an empty
+/// batch is returned.
+async fn read_entries(reader: &FileSystemDirectoryReader) -> Result<Vec<FileSystemEntry>, JsValue> {
+    let mut entries = Vec::new();
+    loop {
+        let promise = Promise::new(&mut |resolve, reject| {
+            if let Err(err) = reader.read_entries_with_callback_and_callback(&resolve, &reject) {
+                reject.call1(&JsValue::NULL, &err).unwrap();
+            }
+        });
+        let batch: Array = JsFuture::from(promise).await?.unchecked_into();
+        if batch.length() == 0 {
+            return Ok(entries);
+        }
+        entries.extend(batch.iter().map(JsCast::unchecked_into));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{transfer_types, DroppableBuilder, FromTransfer};
+use std::{
+    any::{Any, TypeId},
+    ops::Deref,
+};
+use sycamore::prelude::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{DataTransfer, File};
+
+/// A file dropped from outside the browser, like the desktop or a file manager.
+#[derive(Clone, Debug)]
+pub struct DroppedFile(File);
+
+impl DroppedFile {
+    pub(crate) fn new(file: File) -> Self {
+        Self(file)
+    }
+
+    /// The name of the file, without the path.
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.0.size() as u64
+    }
+
+    /// The MIME type of the file, like `image/png`. This is guessed by the browser from the file
+    /// extension and is empty if it's unknown.
+    pub fn mime_type(&self) -> String {
+        self.0.type_()
+    }
+
+    /// When the file was last modified, in milliseconds since the Unix epoch.
+    pub fn last_modified(&self) -> f64 {
+        self.0.last_modified()
+    }
+
+    /// Read the contents of the file as UTF-8 text.
+    pub async fn read_text(&self) -> Result<String, JsValue> {
+        let text = JsFuture::from(self.0.text()).await?;
+        Ok(text.as_string().unwrap_or_default())
+    }
+
+    /// Read the contents of the file as bytes.
+    pub async fn read_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let buffer = JsFuture::from(self.0.array_buffer()).await?;
+        Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+    }
+
+    /// The underlying [`File`], for passing it to other web APIs.
+    pub fn file(&self) -> &File {
+        &self.0
+    }
+}
+
+/// The files dropped on a droppable. Use [`DroppableBuilder::accept_mime`] and the other filters
+/// to limit which files are accepted.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[component]
+/// fn Upload<G: Html>(cx: Scope) -> View<G> {
+///     let drop = create_droppable(cx)
+///         .accept_mime("image/*")
+///         .max_size(10 * 1024 * 1024)
+///         .on_drop_async(|files: DroppedFiles| async move {
+///             for file in files.iter() {
+///                 let bytes = file.read_bytes().await.unwrap();
+///                 log::info!("Uploading {} ({} bytes)", file.name(), bytes.len());
+///             }
+///         })
+///         .build();
+///
+///     view! { cx,
+///         div(class = "upload", ref = drop) { "Drop images here" }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DroppedFiles(Vec<DroppedFile>);
+
+impl Deref for DroppedFiles {
+    type Target = [DroppedFile];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for DroppedFiles {
+    type Item = DroppedFile;
+    type IntoIter = std::vec::IntoIter<DroppedFile>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromTransfer for DroppedFiles {
+    fn from_transfer(transfer: &DataTransfer) -> Option<Self> {
+        let items = transfer.items();
+        let mut files = (0..items.length())
+            .filter_map(|i| items.get(i))
+            .filter(|item| item.kind() == "file")
+            .filter_map(|item| item.get_as_file().ok().flatten())
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            let list = transfer.files()?;
+            files = (0..list.length()).filter_map(|i| list.get(i)).collect();
+        }
+        (!files.is_empty()).then(|| DroppedFiles(files.into_iter().map(DroppedFile).collect()))
+    }
+
+    fn matches_transfer(transfer: &DataTransfer) -> bool {
+        transfer_types(transfer).iter().any(|ty| ty == "Files")
+    }
+}
+
+/// The filters set on a droppable for [`DroppedFiles`].
+#[derive(Default)]
+pub(crate) struct FileFilter {
+    mime_types: Vec<String>,
+    extensions: Vec<String>,
+    max_size: Option<u64>,
+    max_count: Option<usize>,
+}
+
+impl FileFilter {
+    /// Check the files while hovering. Browsers only expose the MIME types of the files at that
+    /// point, so their names and sizes are checked on drop.
+    pub(crate) fn matches_hovering(&self, transfer: &DataTransfer) -> bool {
+        let items = transfer.items();
+        let types = (0..items.length())
+            .filter_map(|i| items.get(i))
+            .filter(|item| item.kind() == "file")
+            .map(|item| item.type_())
+            .collect::<Vec<_>>();
+        if self.max_count.map(|max| types.len() > max).unwrap_or(false) {
+            return false;
+        }
+        // Files with an unknown type might still match one of the extensions.
+        self.mime_types.is_empty()
+            || !self.extensions.is_empty()
+            || types
+                .iter()
+                .all(|ty| ty.is_empty() || self.matches_mime(ty))
+    }
+
+    /// Check a hovering item that will be read as `U`. Only [`DroppedFiles`] are filtered.
+    pub(crate) fn matches_hovering_as<U: 'static>(&self, transfer: &DataTransfer) -> bool {
+        TypeId::of::<U>() != TypeId::of::<DroppedFiles>() || self.matches_hovering(transfer)
+    }
+
+    /// Check a dropped item, returning why it was rejected. Only [`DroppedFiles`] are filtered.
+    pub(crate) fn check_dropped(&self, data: &dyn Any) -> Result<(), String> {
+        match data.downcast_ref::<DroppedFiles>() {
+            Some(files) => self.check(files),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the dropped files, returning why they were rejected.
+    fn check(&self, files: &DroppedFiles) -> Result<(), String> {
+        self.check_count(files.len())?;
+        for file in files.iter() {
+            self.check_file(&file.name(), &file.mime_type(), file.size())?;
+        }
+        Ok(())
+    }
+
+    fn check_count(&self, count: usize) -> Result<(), String> {
+        match self.max_count.filter(|max| count > *max) {
+            Some(max) => Err(format!("At most {max} files can be dropped here")),
+            None => Ok(()),
+        }
+    }
+
+    fn check_file(&self, name: &str, mime_type: &str, size: u64) -> Result<(), String> {
+        if self.max_size.map(|max| size > max).unwrap_or(false) {
+            return Err(format!("{name} is too large"));
+        }
+        let has_filter = !self.mime_types.is_empty() || !self.extensions.is_empty();
+        if has_filter && !self.matches_mime(mime_type) && !self.matches_extension(name) {
+            return Err(format!("{name} has an unsupported type"));
+        }
+        Ok(())
+    }
+
+    fn add_mime_type(&mut self, pattern: &str) {
+        self.mime_types.push(pattern.to_lowercase());
+    }
+
+    fn add_extension(&mut self, extension: &str) {
+        self.extensions
+            .push(extension.trim_start_matches('.').to_lowercase());
+    }
+
+    fn matches_mime(&self, mime_type: &str) -> bool {
+        let mime_type = mime_type.to_lowercase();
+        self.mime_types
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => mime_type.starts_with(prefix),
+                None => mime_type == *pattern,
+            })
+    }
+
+    fn matches_extension(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.extensions
+            .iter()
+            .any(|extension| name.ends_with(&format!(".{extension}")))
+    }
+}
+
+/// Filters for droppables receiving [`DroppedFiles`], either with `on_drop` or `on_files`. Files
+/// that don't pass them aren't accepted while hovering, as far as the browser allows checking
+/// them, and are rejected when dropped. Other types of items aren't filtered.
+impl<'cx, G: Html, T: FromTransfer + 'static> DroppableBuilder<'cx, G, T> {
+    fn file_filter(&mut self) -> &mut FileFilter {
+        self.file_filter.get_or_insert_with(Default::default)
+    }
+
+    /// Only accept files with this MIME type. Patterns like `image/*` match all subtypes. Can be
+    /// called multiple times to accept several types, and files matching either a MIME type or an
+    /// extension are accepted.
+    pub fn accept_mime(mut self, pattern: impl Into<String>) -> Self {
+        self.file_filter().add_mime_type(&pattern.into());
+        self
+    }
+
+    /// Only accept files with one of these extensions, with or without the leading dot.
+    pub fn accept_extensions<S: AsRef<str>>(
+        mut self,
+        extensions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        for extension in extensions {
+            self.file_filter().add_extension(extension.as_ref());
+        }
+        self
+    }
+
+    /// Only accept files up to this size in bytes.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.file_filter().max_size = Some(bytes);
+        self
+    }
+
+    /// Only accept drops of up to this many files.
+    pub fn max_count(mut self, count: usize) -> Self {
+        self.file_filter().max_count = Some(count);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types() {
+        let mut filter = FileFilter::default();
+        filter.add_mime_type("image/*");
+        filter.add_mime_type("Application/PDF");
+        assert!(filter.check_file("cat.png", "image/png", 10).is_ok());
+        assert!(filter.check_file("cat.svg", "image/svg+xml", 10).is_ok());
+        assert!(filter
+            .check_file("report.pdf", "application/pdf", 10)
+            .is_ok());
+        assert!(filter.check_file("notes.txt", "text/plain", 10).is_err());
+        assert!(filter.check_file("unknown", "", 10).is_err());
+    }
+
+    #[test]
+    fn extensions() {
+        let mut filter = FileFilter::default();
+        filter.add_extension(".PNG");
+        filter.add_extension("jpg");
+        assert!(filter.check_file("cat.png", "", 10).is_ok());
+        assert!(filter.check_file("CAT.JPG", "", 10).is_ok());
+        assert!(filter.check_file("cat.png.txt", "", 10).is_err());
+        assert!(filter.check_file("png", "", 10).is_err());
+    }
+
+    #[test]
+    fn mime_type_or_extension() {
+        let mut filter = FileFilter::default();
+        filter.add_mime_type("text/markdown");
+        filter.add_extension("md");
+        assert!(filter.check_file("readme.md", "", 10).is_ok());
+        assert!(filter.check_file("readme", "text/markdown", 10).is_ok());
+        assert!(filter.check_file("readme.txt", "text/plain", 10).is_err());
+    }
+
+    #[test]
+    fn max_size() {
+        let filter = FileFilter {
+            max_size: Some(100),
+            ..Default::default()
+        };
+        assert!(filter.check_file("small.bin", "", 100).is_ok());
+        assert_eq!(
+            filter.check_file("large.bin", "", 101),
+            Err("large.bin is too large".to_string())
+        );
+    }
+
+    #[test]
+    fn max_count() {
+        let filter = FileFilter {
+            max_count: Some(2),
+            ..Default::default()
+        };
+        assert!(filter.check_count(2).is_ok());
+        assert!(filter.check_count(3).is_err());
+        assert!(FileFilter::default().check_count(1000).is_ok());
+    }
+
+    #[test]
+    fn other_types_are_not_filtered() {
+        let filter = FileFilter {
+            max_count: Some(0),
+            ..Default::default()
+        };
+        assert!(filter.check_dropped(&"Hello".to_string()).is_ok());
+    }
+}
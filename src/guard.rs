@@ -0,0 +1,86 @@
+use crate::transfer_types;
+use sycamore::prelude::*;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{DataTransfer, DragEvent, Element, EventTarget};
+
+/// Stop the browser from opening files that are dropped outside of a droppable, which navigates
+/// away from the app. While the scope is alive, dragging files over anything but a droppable shows
+/// that they can't be dropped there, and dropping them does nothing.
+///
+/// Droppables handle the events before they reach the window, so they still receive files as
+/// usual. Only events no droppable accepted are blocked, and file inputs keep working. The guard
+/// does nothing when rendering on the server, which is why it takes the renderer `G`.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[component]
+/// fn App<G: Html>(cx: Scope) -> View<G> {
+///     create_drop_guard::<G>(cx);
+///     let drop = create_droppable(cx)
+///         .on_drop(|files: DroppedFiles| log::info!("Received {} files", files.len()))
+///         .build();
+///
+///     view! { cx,
+///         div(class = "drop-zone", ref = drop) { "Drop files here" }
+///     }
+/// }
+/// ```
+pub fn create_drop_guard<G: Html>(cx: Scope<'_>) {
+    if !G::IS_BROWSER {
+        return;
+    }
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    add_global_listener(cx, &window, "dragover", |e| {
+        if let Some(transfer) = unhandled_files(&e) {
+            e.prevent_default();
+            transfer.set_drop_effect("none");
+        }
+    });
+    add_global_listener(cx, &window, "drop", |e| {
+        if unhandled_files(&e).is_some() {
+            log::trace!("Blocked a file drop outside of a droppable");
+            e.prevent_default();
+        }
+    });
+}
+
+/// Listen to a drag event on a target outside of the component, like the window or document, and
+/// stop listening once the scope is disposed.
+pub(crate) fn add_global_listener(
+    cx: Scope<'_>,
+    target: &EventTarget,
+    event: &'static str,
+    f: impl Fn(DragEvent) + 'static,
+) {
+    let listener = Closure::<dyn Fn(DragEvent)>::new(f);
+    target
+        .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+        .unwrap();
+
+    let target = target.clone();
+    on_cleanup(cx, move || {
+        target
+            .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+            .unwrap();
+    });
+}
+
+/// Get the [`DataTransfer`] of an event carrying files that no droppable or file input accepted.
+fn unhandled_files(e: &DragEvent) -> Option<DataTransfer> {
+    let on_file_input = e
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .map(|target| target.matches("input[type=file]").unwrap_or(false))
+        .unwrap_or(false);
+    if e.default_prevented() || on_file_input {
+        return None;
+    }
+    e.data_transfer()
+        .filter(|transfer| transfer_types(transfer).iter().any(|ty| ty == "Files"))
+}
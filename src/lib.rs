@@ -2,9 +2,9 @@
 //!
 //! Adds the [`create_draggable`] and [`create_droppable`] functions that abstract the difficult
 //! parts of drag and drop.
-//! This library is still fairly low level and makes no assumptions about drop behaviour. This makes
-//! it possible to do things like reading a file, but requires custom code for things like sortable
-//! lists or other common drag and drop scenarios.
+//! These are fairly low level and make no assumptions about drop behaviour. This makes it possible
+//! to do things like reading a file, while [`create_sortable`] and [`create_tree`] cover the common
+//! cases of reordering a list or moving nodes in a tree.
 //!
 //! # Example Usage
 //!
@@ -59,17 +59,38 @@
 #![deny(missing_docs)]
 
 use serde::{de::DeserializeOwned, Serialize};
-use std::ops::Deref;
+use std::{any::Any, cell::Cell, ops::Deref};
+use sycamore::reactive::Signal;
 
+mod context;
 mod drag;
 mod drop;
+mod entries;
+mod files;
+mod guard;
+mod overlay;
+mod pointer;
+mod position;
+mod sortable;
+mod transfer;
+mod tree;
 
+pub use context::*;
 pub use drag::*;
 pub use drop::*;
+pub use entries::*;
+pub use files::*;
+pub use guard::*;
+pub use overlay::*;
+pub use pointer::DragBackend;
+pub use position::*;
+pub use sortable::*;
+pub use transfer::*;
+pub use tree::*;
 pub use web_sys::DataTransfer;
 
 /// The effect allowed when dropping an item.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
 pub enum DropEffect {
     /// No effect
     None,
@@ -103,6 +124,88 @@ impl DropEffect {
             DropEffect::All => "all",
         }
     }
+
+    /// Parse a `dropEffect` or `effectAllowed` value. Unknown values are treated as
+    /// [`DropEffect::None`].
+    fn from_js(effect: &str) -> Self {
+        match effect {
+            "copy" => DropEffect::Copy,
+            "copyLink" => DropEffect::CopyLink,
+            "copyMove" => DropEffect::CopyMove,
+            "link" => DropEffect::Link,
+            "linkMove" => DropEffect::LinkMove,
+            "move" => DropEffect::Move,
+            "all" => DropEffect::All,
+            _ => DropEffect::None,
+        }
+    }
+}
+
+/// The prefix of the MIME types used to tag the Rust type of a transfer.
+const TYPE_TAG_PREFIX: &str = "application/x-sycamore-dnd+";
+
+/// The MIME type used to tag a [`DataTransfer`] containing a serialized `T`.
+///
+/// Browsers only expose the list of types while an item is dragged over an element, so this tag is
+/// what lets droppables decide whether they accept an item before it's dropped. References are
+/// tagged the same as the type they point to, and `str` is tagged as `String`, so
+/// `.data("Hello")` matches a droppable that reads a `String`.
+/// Use this when writing a custom [`AsTransfer`] implementation or `set_data` callback.
+///
+/// The tag is built from [`std::any::type_name`], which isn't guaranteed to be unique or to stay
+/// the same between compiler versions. That's fine for drags within an app, where both sides come
+/// from the same build, but don't rely on it for drags between separately built apps. Write your
+/// own MIME type with `set_data` and read it with [`RawTransfer`] for those.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore_dnd::*;
+/// assert_eq!(transfer_tag::<&str>(), transfer_tag::<String>());
+/// assert_eq!(transfer_tag::<&mut i32>(), transfer_tag::<i32>());
+/// assert_eq!(transfer_tag::<i32>(), "application/x-sycamore-dnd+i32");
+/// ```
+pub fn transfer_tag<T: ?Sized>() -> String {
+    let mut name = std::any::type_name::<T>();
+    while let Some(referent) = name.strip_prefix('&') {
+        name = referent.strip_prefix("mut ").unwrap_or(referent);
+    }
+    let name = if name == "str" {
+        std::any::type_name::<String>()
+    } else {
+        name
+    };
+    format!("{TYPE_TAG_PREFIX}{}", name.to_ascii_lowercase())
+}
+
+/// Get the list of types in a [`DataTransfer`]. Unlike the data, these are also available during
+/// `dragenter` and `dragover`.
+pub(crate) fn transfer_types(transfer: &DataTransfer) -> Vec<String> {
+    transfer
+        .types()
+        .iter()
+        .filter_map(|ty| ty.as_string())
+        .collect()
+}
+
+/// Set a signal only if the value changed, to avoid notifying subscribers on every drag event.
+pub(crate) fn set_if_changed<T: PartialEq>(signal: &Signal<T>, value: T) {
+    if *signal.get_untracked() != value {
+        signal.set(value);
+    }
+}
+
+thread_local! {
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A new id for a sortable list or tree, to tell the items dragged in it from those of others.
+pub(crate) fn next_id() -> u64 {
+    NEXT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    })
 }
 
 /// A trait implemented for any value that can be written to a drag and drop [`DataTransfer`]
@@ -116,6 +219,7 @@ impl<T: Serialize> AsTransfer for T {
         transfer
             .set_data("data/json", &serde_json::to_string(self).unwrap())
             .unwrap();
+        transfer.set_data(&transfer_tag::<T>(), "").unwrap();
     }
 }
 
@@ -125,6 +229,32 @@ impl<T: Serialize> AsTransfer for T {
 pub trait FromTransfer: Sized {
     /// Read the data from the [`DataTransfer`]
     fn from_transfer(transfer: &DataTransfer) -> Option<Self>;
+
+    /// Check whether the [`DataTransfer`] looks like it contains this type, without reading the
+    /// data. This is used while hovering, where browsers hide the data and only expose the types.
+    ///
+    /// The default implementation checks for the [`transfer_tag`] of this type, or JSON data. JSON
+    /// written for another type, or by a custom `set_data` without a tag, is assumed to match,
+    /// since it can still deserialize into this type (like a `u32` into a `u64`), and is checked
+    /// when dropped. Implement this to only match the exact tag, or use [`RawTransfer`] to inspect
+    /// the transfer yourself.
+    fn matches_transfer(transfer: &DataTransfer) -> bool {
+        let tag = transfer_tag::<Self>();
+        let types = transfer_types(transfer);
+        types.contains(&tag) || types.iter().any(|ty| ty == "data/json")
+    }
+
+    /// Read the data from an in-memory value set with [`DraggableBuilder::value`]. This is
+    /// preferred over [`FromTransfer::from_transfer`] when the drag started in the same
+    /// [`DndContext`].
+    ///
+    /// The default implementation returns the value if it's of this type.
+    fn from_value(value: Box<dyn Any>) -> Option<Self>
+    where
+        Self: 'static,
+    {
+        value.downcast().ok().map(|value| *value)
+    }
 }
 
 /// A wrapper type for a raw [`DataTransfer`]
@@ -142,6 +272,36 @@ impl FromTransfer for RawTransfer {
     fn from_transfer(transfer: &DataTransfer) -> Option<Self> {
         Some(RawTransfer(transfer.clone()))
     }
+
+    fn matches_transfer(_transfer: &DataTransfer) -> bool {
+        true
+    }
+}
+
+/// A wrapper type for values that can only be received in memory, through a [`DndContext`].
+/// Use this to receive types that can't be deserialized, like `Rc`, signals or closures.
+pub struct InMemory<T>(pub T);
+
+impl<T> Deref for InMemory<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: 'static> FromTransfer for InMemory<T> {
+    fn from_transfer(_transfer: &DataTransfer) -> Option<Self> {
+        None
+    }
+
+    fn matches_transfer(_transfer: &DataTransfer) -> bool {
+        false
+    }
+
+    fn from_value(value: Box<dyn Any>) -> Option<Self> {
+        value.downcast().ok().map(|value| InMemory(*value))
+    }
 }
 
 impl<T: DeserializeOwned> FromTransfer for T {
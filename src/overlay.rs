@@ -0,0 +1,155 @@
+use crate::{
+    create_droppable, guard::add_global_listener, set_if_changed, DroppableBuilder, DroppedFiles,
+    FromTransfer, IntoDropResult,
+};
+use std::{cell::Cell, future::Future, rc::Rc};
+use sycamore::prelude::*;
+
+/// The style of the overlay while a matching item is dragged over the page.
+const VISIBLE_STYLE: &str = "position:fixed;inset:0;z-index:2147483647;";
+/// The style of the overlay while nothing is dragged over the page.
+const HIDDEN_STYLE: &str = "display:none;";
+
+/// The builder for the [`create_drop_overlay`] options
+pub struct DropOverlayBuilder<'cx, G: Html, T: FromTransfer + 'static = DroppedFiles> {
+    scope: Scope<'cx>,
+    droppable: DroppableBuilder<'cx, G, T>,
+    visible: &'cx RcSignal<bool>,
+    class: String,
+}
+
+impl<'cx, G: Html, T: FromTransfer + 'static> DropOverlayBuilder<'cx, G, T> {
+    fn new(scope: Scope<'cx>) -> Self {
+        Self {
+            scope,
+            droppable: create_droppable(scope),
+            visible: create_ref(scope, create_rc_signal(false)),
+            class: Default::default(),
+        }
+    }
+
+    /// Sets a callback to run when an item is dropped on the overlay. See
+    /// [`DroppableBuilder::on_drop`].
+    pub fn on_drop<R: IntoDropResult>(mut self, f: impl Fn(T) -> R + 'cx) -> Self {
+        self.droppable = self.droppable.on_drop(f);
+        self
+    }
+
+    /// Sets an async callback to run when an item is dropped on the overlay. See
+    /// [`DroppableBuilder::on_drop_async`].
+    pub fn on_drop_async<F: Future<Output = ()> + 'cx>(self, f: impl Fn(T) -> F + 'cx) -> Self {
+        self.droppable(|droppable| droppable.on_drop_async(f))
+    }
+
+    /// Configure the droppable behind the overlay, e.g. to set a `hovering_class` or filters for
+    /// [`DroppedFiles`].
+    pub fn droppable(
+        mut self,
+        f: impl FnOnce(DroppableBuilder<'cx, G, T>) -> DroppableBuilder<'cx, G, T>,
+    ) -> Self {
+        self.droppable = f(self.droppable);
+        self
+    }
+
+    /// A class or list of classes to set on the overlay element.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = class.into();
+        self
+    }
+
+    /// A signal that is `true` while the overlay is shown.
+    pub fn is_visible(&self) -> &'cx ReadSignal<bool> {
+        self.visible
+    }
+
+    /// Create the overlay, showing `view` inside it. The returned view needs to be rendered
+    /// somewhere in the app and covers the whole page while it's visible.
+    pub fn build(self, view: View<G>) -> View<G> {
+        let cx = self.scope;
+        let visible = self.visible;
+        if let Some(document) = G::IS_BROWSER
+            .then(web_sys::window)
+            .flatten()
+            .and_then(|window| window.document())
+        {
+            // `dragenter` and `dragleave` fire for every element on the page, so count them to
+            // know when the item leaves the window.
+            let depth = Rc::new(Cell::new(0u32));
+            add_global_listener(cx, &document, "dragenter", {
+                let (depth, visible) = (depth.clone(), visible.clone());
+                move |e| {
+                    let matches = e
+                        .data_transfer()
+                        .map(|transfer| T::matches_transfer(&transfer))
+                        .unwrap_or(false);
+                    if matches {
+                        depth.set(depth.get() + 1);
+                        set_if_changed(&visible, true);
+                    }
+                }
+            });
+            add_global_listener(cx, &document, "dragleave", {
+                let (depth, visible) = (depth.clone(), visible.clone());
+                move |_| {
+                    if depth.get() == 0 {
+                        return;
+                    }
+                    depth.set(depth.get() - 1);
+                    if depth.get() == 0 {
+                        set_if_changed(&visible, false);
+                    }
+                }
+            });
+            for event in ["drop", "dragend"] {
+                let (depth, visible) = (depth.clone(), visible.clone());
+                add_global_listener(cx, &document, event, move |_| {
+                    depth.set(0);
+                    set_if_changed(&visible, false);
+                });
+            }
+        }
+
+        let node = self.droppable.build();
+        let class = self.class;
+        view! { cx,
+            div(
+                ref = node,
+                class = class,
+                style = if *visible.get() { VISIBLE_STYLE } else { HIDDEN_STYLE },
+            ) {
+                (view)
+            }
+        }
+    }
+}
+
+/// Create a full-page overlay that appears while an item that can be read as `T` is dragged over
+/// the page, like files for an upload. Items dropped anywhere on the page are passed to the
+/// overlay's `on_drop` callback.
+///
+/// Combine it with [`create_drop_guard`](crate::create_drop_guard) so files the overlay rejects
+/// don't make the browser navigate away.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[component]
+/// fn App<G: Html>(cx: Scope) -> View<G> {
+///     let overlay = create_drop_overlay(cx)
+///         .on_drop(|files: DroppedFiles| log::info!("Uploading {} files", files.len()))
+///         .class("drop-overlay")
+///         .build(view! { cx, p { "Drop files anywhere to upload them" } });
+///
+///     view! { cx,
+///         main { "Your files" }
+///         (overlay)
+///     }
+/// }
+/// ```
+pub fn create_drop_overlay<G: Html, T: FromTransfer>(
+    cx: Scope<'_>,
+) -> DropOverlayBuilder<'_, G, T> {
+    DropOverlayBuilder::new(cx)
+}
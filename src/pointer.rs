@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use sycamore::{prelude::*, web::html::ev};
+use wasm_bindgen::JsCast;
+use web_sys::{DataTransfer, DragEvent, DragEventInit, Element, HtmlElement, PointerEvent};
+
+/// The distance in pixels the pointer needs to move before a pointer drag is started. This keeps
+/// taps and clicks on draggable elements working as usual.
+const DRAG_THRESHOLD: i32 = 4;
+
+/// The event source used to drive a draggable element.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum DragBackend {
+    /// Use the browser's native HTML5 drag and drop events. This supports dragging between
+    /// windows and applications, but most touch browsers never fire these events.
+    #[default]
+    Native,
+    /// Emulate drag and drop with pointer events. This works with mouse, touch and pen input, but
+    /// drags are limited to the current document.
+    Pointer,
+    /// Use [`DragBackend::Pointer`] on devices with a touch screen and [`DragBackend::Native`]
+    /// everywhere else.
+    Auto,
+}
+
+impl DragBackend {
+    /// Resolve [`DragBackend::Auto`] to a concrete backend using feature detection.
+    pub(crate) fn resolve(self) -> Self {
+        match self {
+            DragBackend::Auto => {
+                let has_touch = web_sys::window()
+                    .map(|window| window.navigator().max_touch_points() > 0)
+                    .unwrap_or(false);
+                if has_touch {
+                    DragBackend::Pointer
+                } else {
+                    DragBackend::Native
+                }
+            }
+            backend => backend,
+        }
+    }
+}
+
+struct PointerDrag {
+    pointer_id: i32,
+    start: (i32, i32),
+    session: Option<DragSession>,
+}
+
+struct DragSession {
+    transfer: DataTransfer,
+    ghost: HtmlElement,
+    offset: (i32, i32),
+    target: Option<Element>,
+    accepted: bool,
+}
+
+/// Emulates the HTML5 drag and drop events with pointer events. Droppables don't need to know
+/// about this backend - they receive synthetic `dragenter`, `dragover`, `dragleave` and `drop`
+/// events on the element under the pointer, just like they would with a native drag.
+pub(crate) fn create_pointer_drag<'cx, G: Html>(
+    cx: Scope<'cx>,
+    node: &G,
+    drag_image: &'cx RefCell<Option<(Element, i32, i32)>>,
+    can_start: impl Fn(&PointerEvent) -> bool + 'cx,
+) {
+    let state = create_ref(cx, RefCell::new(None::<PointerDrag>));
+
+    let on_pointer_down = move |e: PointerEvent| {
+        if !e.is_primary() || e.button() != 0 || state.borrow().is_some() || !can_start(&e) {
+            return;
+        }
+        *state.borrow_mut() = Some(PointerDrag {
+            pointer_id: e.pointer_id(),
+            start: (e.client_x(), e.client_y()),
+            session: None,
+        });
+    };
+
+    let on_pointer_move = {
+        let node = node.clone();
+        move |e: PointerEvent| {
+            let mut state = state.borrow_mut();
+            let Some(drag) = state
+                .as_mut()
+                .filter(|drag| drag.pointer_id == e.pointer_id())
+            else {
+                return;
+            };
+            let position = (e.client_x(), e.client_y());
+            if drag.session.is_none() {
+                let (dx, dy) = (position.0 - drag.start.0, position.1 - drag.start.1);
+                if dx.abs() < DRAG_THRESHOLD && dy.abs() < DRAG_THRESHOLD {
+                    return;
+                }
+                let element = node.to_web_sys().unchecked_into::<Element>();
+                match start_session(&element, drag.start, drag_image) {
+                    Some(session) => drag.session = Some(session),
+                    None => {
+                        log::trace!("Pointer drag cancelled");
+                        *state = None;
+                        return;
+                    }
+                }
+                // Only capture the pointer once the drag started, so clicks on buttons or links
+                // inside the element still reach them.
+                element.set_pointer_capture(drag.pointer_id).ok();
+            }
+
+            e.prevent_default();
+            let element = node.to_web_sys().unchecked_into::<Element>();
+            let session = drag.session.as_mut().unwrap();
+            dispatch(&element, "drag", &session.transfer, position);
+            session.update(position);
+        }
+    };
+
+    let on_pointer_end = move |cancelled: bool| {
+        let node = node.clone();
+        move |e: PointerEvent| {
+            let Some(drag) = state.take() else {
+                return;
+            };
+            if drag.pointer_id != e.pointer_id() {
+                *state.borrow_mut() = Some(drag);
+                return;
+            }
+            let element = node.to_web_sys().unchecked_into::<Element>();
+            if let Some(session) = drag.session {
+                element.release_pointer_capture(drag.pointer_id).ok();
+                session.finish(&element, (e.client_x(), e.client_y()), cancelled);
+            }
+        }
+    };
+
+    node.remove_attribute("draggable".into());
+    node.event(cx, ev::pointerdown, on_pointer_down);
+    node.event(cx, ev::pointermove, on_pointer_move);
+    node.event(cx, ev::pointerup, on_pointer_end(false));
+    node.event(cx, ev::pointercancel, on_pointer_end(true));
+}
+
+fn start_session(
+    element: &Element,
+    start: (i32, i32),
+    drag_image: &RefCell<Option<(Element, i32, i32)>>,
+) -> Option<DragSession> {
+    let transfer = DataTransfer::new().ok()?;
+    if !dispatch(element, "dragstart", &transfer, start) {
+        return None;
+    }
+
+    let Some((ghost, offset)) = create_ghost(element, start, drag_image) else {
+        // The draggable already started the drag, so it needs to hear that it ended.
+        log::warn!("Could not create the pointer drag preview, cancelling the drag");
+        transfer.set_drop_effect("none");
+        dispatch(element, "dragend", &transfer, start);
+        return None;
+    };
+    let mut session = DragSession {
+        transfer,
+        ghost,
+        offset,
+        target: None,
+        accepted: false,
+    };
+    session.update(start);
+    Some(session)
+}
+
+/// Create the element that follows the pointer during a drag, and its offset from the pointer.
+fn create_ghost(
+    element: &Element,
+    start: (i32, i32),
+    drag_image: &RefCell<Option<(Element, i32, i32)>>,
+) -> Option<(HtmlElement, (i32, i32))> {
+    // The drag image is only known after `dragstart`, since it can be rendered on demand.
+    let drag_image = drag_image.borrow().clone();
+    let (source, offset) = match drag_image {
+        Some((ref image, x, y)) => (image, (x, y)),
+        None => {
+            let rect = element.get_bounding_client_rect();
+            let offset = (start.0 - rect.x() as i32, start.1 - rect.y() as i32);
+            (element, offset)
+        }
+    };
+    let ghost = source
+        .clone_node_with_deep(true)
+        .ok()?
+        .unchecked_into::<HtmlElement>();
+    let style = ghost.style();
+    for (property, value) in [
+        ("position", "fixed"),
+        ("left", "0"),
+        ("top", "0"),
+        ("margin", "0"),
+        ("pointer-events", "none"),
+        ("z-index", "2147483647"),
+        ("opacity", "0.7"),
+    ] {
+        style.set_property(property, value).ok();
+    }
+    if drag_image.is_none() {
+        let width = element.get_bounding_client_rect().width();
+        style.set_property("width", &format!("{width}px")).ok();
+    }
+    ghost.remove_attribute("id").ok();
+    web_sys::window()?
+        .document()?
+        .body()?
+        .append_child(&ghost)
+        .ok()?;
+    Some((ghost, offset))
+}
+
+impl DragSession {
+    /// Move the drag preview and send the drag events to the element under the pointer.
+    fn update(&mut self, position: (i32, i32)) {
+        self.ghost
+            .style()
+            .set_property(
+                "transform",
+                &format!(
+                    "translate({}px, {}px)",
+                    position.0 - self.offset.0,
+                    position.1 - self.offset.1
+                ),
+            )
+            .ok();
+
+        let hit = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.element_from_point(position.0 as f32, position.1 as f32));
+        if hit != self.target {
+            // Browsers fire `dragenter` on the new target before `dragleave` on the old one.
+            if let Some(target) = hit.as_ref() {
+                dispatch(target, "dragenter", &self.transfer, position);
+            }
+            if let Some(target) = self.target.as_ref() {
+                dispatch(target, "dragleave", &self.transfer, position);
+            }
+            self.target = hit;
+        }
+
+        // A droppable accepts the item by cancelling `dragover`, same as with native events.
+        self.accepted = self
+            .target
+            .as_ref()
+            .map(|target| !dispatch(target, "dragover", &self.transfer, position))
+            .unwrap_or(false);
+    }
+
+    /// Drop the item on the current target if it was accepted and end the drag.
+    fn finish(self, source: &Element, position: (i32, i32), cancelled: bool) {
+        self.ghost.remove();
+        let mut dropped = false;
+        if let Some(target) = self.target.as_ref() {
+            if self.accepted && !cancelled {
+                // Unlike the browser, a constructed `DataTransfer` has no default drop effect, so
+                // pick the one a native drag would use. The droppable can still change it, e.g.
+                // to `none` when it rejects the item.
+                let effect = match self.transfer.effect_allowed().as_str() {
+                    "link" | "linkMove" => "link",
+                    "move" => "move",
+                    _ => "copy",
+                };
+                self.transfer.set_drop_effect(effect);
+                dropped = !dispatch(target, "drop", &self.transfer, position);
+            } else {
+                dispatch(target, "dragleave", &self.transfer, position);
+            }
+        }
+        if !dropped {
+            self.transfer.set_drop_effect("none");
+        }
+        dispatch(source, "dragend", &self.transfer, position);
+    }
+}
+
+/// Dispatches a synthetic [`DragEvent`] and returns `false` if it was cancelled.
+fn dispatch(target: &Element, name: &str, transfer: &DataTransfer, position: (i32, i32)) -> bool {
+    let mut init = DragEventInit::new();
+    init.bubbles(true)
+        .cancelable(true)
+        .client_x(position.0)
+        .client_y(position.1)
+        .data_transfer(Some(transfer));
+    DragEvent::new_with_event_init_dict(name, &init)
+        .map(|event| target.dispatch_event(&event).unwrap_or(true))
+        .unwrap_or(true)
+}
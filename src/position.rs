@@ -0,0 +1,360 @@
+use web_sys::{DomRect, Element, MouseEvent};
+
+/// An edge of an element.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Edge {
+    /// The top edge
+    Top,
+    /// The bottom edge
+    Bottom,
+    /// The left edge
+    Left,
+    /// The right edge
+    Right,
+}
+
+/// The axis along which items are laid out, used to decide if an item goes before or after the
+/// element it's dropped on.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    /// Items are laid out top to bottom, like a list.
+    #[default]
+    Vertical,
+    /// Items are laid out left to right, like a row of tabs.
+    Horizontal,
+    /// Items are laid out left to right and wrap into rows, like a grid of photos. An item goes
+    /// before or after the element depending on which half of it it's dropped on, so dropping
+    /// inside doesn't apply.
+    Grid,
+}
+
+/// Where a dropped item should go relative to the element it was dropped on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Placement {
+    /// Before the element
+    Before,
+    /// Inside the element, i.e. as a child in a tree
+    Inside,
+    /// After the element
+    After,
+}
+
+/// The fractions of an element along the [`Orientation`] axis that count as dropping before or
+/// after it. Anything in between counts as dropping inside.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PlacementThresholds {
+    /// Positions before this fraction are [`Placement::Before`].
+    pub before: f64,
+    /// Positions from this fraction on are [`Placement::After`].
+    pub after: f64,
+}
+
+impl PlacementThresholds {
+    /// Create thresholds, clamped to the element and so `before` is never after `after`.
+    pub(crate) fn new(before: f64, after: f64) -> Self {
+        if !(0.0..=after).contains(&before) || after > 1.0 {
+            log::warn!("Invalid placement thresholds {before} and {after}, clamping them");
+        }
+        let before = before.clamp(0.0, 1.0);
+        Self {
+            before,
+            after: after.max(before).min(1.0),
+        }
+    }
+}
+
+impl Default for PlacementThresholds {
+    /// Splits the element in half, so items are never dropped inside.
+    fn default() -> Self {
+        Self {
+            before: 0.5,
+            after: 0.5,
+        }
+    }
+}
+
+/// The bounds of an element relative to the viewport, like a [`DomRect`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<DomRect> for Rect {
+    fn from(rect: DomRect) -> Self {
+        Self {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+/// Where inside a droppable an item was dropped, or is hovering.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DropPosition {
+    /// The horizontal position relative to the viewport
+    pub client_x: f64,
+    /// The vertical position relative to the viewport
+    pub client_y: f64,
+    /// The horizontal position relative to the left edge of the element
+    pub x: f64,
+    /// The vertical position relative to the top edge of the element
+    pub y: f64,
+    /// The width of the element
+    pub width: f64,
+    /// The height of the element
+    pub height: f64,
+    /// The edge of the element closest to the position
+    pub edge: Edge,
+    /// Where the item goes relative to the element
+    pub placement: Placement,
+}
+
+impl DropPosition {
+    /// Calculate the position of an event inside an element.
+    pub(crate) fn from_event(
+        e: &MouseEvent,
+        element: &Element,
+        orientation: Orientation,
+        thresholds: PlacementThresholds,
+    ) -> Self {
+        let rtl = orientation != Orientation::Vertical && is_rtl(element);
+        Self::new(
+            e.client_x() as f64,
+            e.client_y() as f64,
+            &element.get_bounding_client_rect().into(),
+            orientation,
+            thresholds,
+            rtl,
+        )
+    }
+
+    /// Calculate the position inside an element. With `rtl`, the horizontal axis is flipped, so
+    /// items further left go after the element.
+    pub(crate) fn new(
+        client_x: f64,
+        client_y: f64,
+        rect: &Rect,
+        orientation: Orientation,
+        thresholds: PlacementThresholds,
+        rtl: bool,
+    ) -> Self {
+        let (width, height) = (rect.width, rect.height);
+        let (x, y) = (client_x - rect.x, client_y - rect.y);
+
+        let edge = [
+            (y, Edge::Top),
+            (height - y, Edge::Bottom),
+            (x, Edge::Left),
+            (width - x, Edge::Right),
+        ]
+        .into_iter()
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, edge)| edge)
+        .unwrap();
+
+        let placement = if orientation == Orientation::Grid {
+            grid_placement(rect, client_x, client_y, rtl)
+        } else {
+            let fraction = match orientation {
+                Orientation::Vertical if height > 0.0 => y / height,
+                Orientation::Horizontal if width > 0.0 => x / width,
+                _ => 0.5,
+            };
+            let fraction = if rtl && orientation == Orientation::Horizontal {
+                1.0 - fraction
+            } else {
+                fraction
+            };
+            if fraction < thresholds.before {
+                Placement::Before
+            } else if fraction >= thresholds.after {
+                Placement::After
+            } else {
+                Placement::Inside
+            }
+        };
+
+        Self {
+            client_x,
+            client_y,
+            x,
+            y,
+            width,
+            height,
+            edge,
+            placement,
+        }
+    }
+}
+
+/// Decide if a position goes before or after an element in a grid, where items wrap into rows.
+/// Positions in the rows above or below the element go before or after it, otherwise the half of
+/// the element it's in decides.
+pub(crate) fn grid_placement(rect: &Rect, client_x: f64, client_y: f64, rtl: bool) -> Placement {
+    let before = if client_y < rect.y {
+        true
+    } else if client_y > rect.y + rect.height {
+        false
+    } else {
+        (client_x < rect.x + rect.width / 2.0) != rtl
+    };
+    if before {
+        Placement::Before
+    } else {
+        Placement::After
+    }
+}
+
+/// Whether the text direction of an element is right to left.
+pub(crate) fn is_rtl(element: &Element) -> bool {
+    web_sys::window()
+        .and_then(|window| window.get_computed_style(element).ok().flatten())
+        .and_then(|style| style.get_property_value("direction").ok())
+        .map(|direction| direction == "rtl")
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECT: Rect = Rect {
+        x: 100.0,
+        y: 100.0,
+        width: 200.0,
+        height: 100.0,
+    };
+
+    fn placement(x: f64, y: f64, orientation: Orientation, before: f64, after: f64) -> Placement {
+        let thresholds = PlacementThresholds::new(before, after);
+        DropPosition::new(x, y, &RECT, orientation, thresholds, false).placement
+    }
+
+    #[test]
+    fn vertical_placement() {
+        assert_eq!(
+            placement(200.0, 110.0, Orientation::Vertical, 0.5, 0.5),
+            Placement::Before
+        );
+        assert_eq!(
+            placement(200.0, 190.0, Orientation::Vertical, 0.5, 0.5),
+            Placement::After
+        );
+        // The midpoint counts as after, so there is no gap between the halves.
+        assert_eq!(
+            placement(200.0, 150.0, Orientation::Vertical, 0.5, 0.5),
+            Placement::After
+        );
+        assert_eq!(
+            placement(200.0, 150.0, Orientation::Vertical, 0.25, 0.75),
+            Placement::Inside
+        );
+        assert_eq!(
+            placement(200.0, 120.0, Orientation::Vertical, 0.25, 0.75),
+            Placement::Before
+        );
+        assert_eq!(
+            placement(200.0, 175.0, Orientation::Vertical, 0.25, 0.75),
+            Placement::After
+        );
+    }
+
+    #[test]
+    fn horizontal_placement() {
+        assert_eq!(
+            placement(120.0, 190.0, Orientation::Horizontal, 0.5, 0.5),
+            Placement::Before
+        );
+        assert_eq!(
+            placement(280.0, 110.0, Orientation::Horizontal, 0.5, 0.5),
+            Placement::After
+        );
+
+        let thresholds = PlacementThresholds::default();
+        let position = DropPosition::new(
+            120.0,
+            150.0,
+            &RECT,
+            Orientation::Horizontal,
+            thresholds,
+            true,
+        );
+        assert_eq!(position.placement, Placement::After);
+        let position = DropPosition::new(
+            280.0,
+            150.0,
+            &RECT,
+            Orientation::Horizontal,
+            thresholds,
+            true,
+        );
+        assert_eq!(position.placement, Placement::Before);
+        // Vertical lists aren't affected by the text direction.
+        let position =
+            DropPosition::new(280.0, 110.0, &RECT, Orientation::Vertical, thresholds, true);
+        assert_eq!(position.placement, Placement::Before);
+    }
+
+    #[test]
+    fn closest_edge() {
+        let edge = |x, y| {
+            let thresholds = PlacementThresholds::default();
+            DropPosition::new(x, y, &RECT, Orientation::Vertical, thresholds, false).edge
+        };
+        assert_eq!(edge(200.0, 101.0), Edge::Top);
+        assert_eq!(edge(200.0, 199.0), Edge::Bottom);
+        assert_eq!(edge(101.0, 150.0), Edge::Left);
+        assert_eq!(edge(299.0, 150.0), Edge::Right);
+    }
+
+    #[test]
+    fn relative_position() {
+        let thresholds = PlacementThresholds::default();
+        let position = DropPosition::new(
+            150.0,
+            125.0,
+            &RECT,
+            Orientation::Vertical,
+            thresholds,
+            false,
+        );
+        assert_eq!((position.x, position.y), (50.0, 25.0));
+        assert_eq!((position.width, position.height), (200.0, 100.0));
+    }
+
+    #[test]
+    fn grid() {
+        assert_eq!(
+            grid_placement(&RECT, 120.0, 150.0, false),
+            Placement::Before
+        );
+        assert_eq!(grid_placement(&RECT, 280.0, 150.0, false), Placement::After);
+        // Rows above and below decide regardless of the horizontal position.
+        assert_eq!(grid_placement(&RECT, 500.0, 50.0, false), Placement::Before);
+        assert_eq!(grid_placement(&RECT, 0.0, 250.0, false), Placement::After);
+        assert_eq!(grid_placement(&RECT, 120.0, 150.0, true), Placement::After);
+        assert_eq!(grid_placement(&RECT, 280.0, 150.0, true), Placement::Before);
+        assert_eq!(grid_placement(&RECT, 500.0, 50.0, true), Placement::Before);
+
+        let thresholds = PlacementThresholds::new(0.25, 0.75);
+        let position = DropPosition::new(200.0, 150.0, &RECT, Orientation::Grid, thresholds, false);
+        assert_eq!(position.placement, Placement::After);
+    }
+
+    #[test]
+    fn thresholds_are_clamped() {
+        let thresholds = PlacementThresholds::new(0.8, 0.2);
+        assert_eq!((thresholds.before, thresholds.after), (0.8, 0.8));
+        let thresholds = PlacementThresholds::new(-1.0, 2.0);
+        assert_eq!((thresholds.before, thresholds.after), (0.0, 1.0));
+        assert_eq!(
+            placement(200.0, 150.0, Orientation::Vertical, 0.8, 0.2),
+            Placement::Before
+        );
+    }
+}
@@ -0,0 +1,796 @@
+use crate::{
+    create_draggable, create_droppable, next_id,
+    position::{grid_placement, is_rtl},
+    set_if_changed, use_dnd_context, DragBackend, DropEffect, InMemory, Orientation, Placement,
+};
+use std::cell::{Cell, RefCell};
+use sycamore::{futures::spawn_local_scoped, prelude::*, web::html::ev};
+use wasm_bindgen::JsCast;
+use web_sys::{DragEvent, Element};
+
+/// The in-memory value of an item dragged in a sortable list.
+#[derive(Clone)]
+struct SortableItem<T, K> {
+    list: u64,
+    group: Option<u64>,
+    key: K,
+    item: T,
+}
+
+/// A group of sortable lists that items can be moved between, like the columns of a kanban board.
+/// Lists join the group with [`SortableBuilder::group`].
+pub struct SortableGroup<K: 'static> {
+    id: u64,
+    /// The list and key of the last item moved to another list, until the source list removed it
+    moved: RefCell<Option<(u64, K)>>,
+}
+
+/// Create a [`SortableGroup`]. Create it in a scope that outlives all lists in the group, like the
+/// component of the whole board.
+pub fn create_sortable_group<K: 'static>(cx: Scope<'_>) -> &SortableGroup<K> {
+    create_ref(
+        cx,
+        SortableGroup {
+            id: next_id(),
+            moved: RefCell::new(None),
+        },
+    )
+}
+
+/// The builder for the [`create_sortable`] options
+pub struct SortableBuilder<'cx, T: 'static, K: 'static> {
+    scope: Scope<'cx>,
+    items: &'cx Signal<Vec<T>>,
+    key_fn: Box<dyn Fn(&T) -> K + 'cx>,
+    orientation: Orientation,
+    backend: DragBackend,
+    dragging_class: String,
+    hovering_class: String,
+    before_class: String,
+    after_class: String,
+    placeholder_class: String,
+    on_reorder: Option<Box<dyn Fn(usize, usize) + 'cx>>,
+    group: Option<&'cx SortableGroup<K>>,
+    #[allow(clippy::type_complexity)]
+    on_receive: Option<Box<dyn Fn(T, usize) -> Option<T> + 'cx>>,
+    on_remove: Option<Box<dyn Fn(T, usize) + 'cx>>,
+}
+
+impl<'cx, T: Clone + 'static, K: PartialEq + Clone + 'static> SortableBuilder<'cx, T, K> {
+    fn new(scope: Scope<'cx>, items: &'cx Signal<Vec<T>>, key_fn: impl Fn(&T) -> K + 'cx) -> Self {
+        Self {
+            scope,
+            items,
+            key_fn: Box::new(key_fn),
+            orientation: Default::default(),
+            backend: Default::default(),
+            dragging_class: Default::default(),
+            hovering_class: Default::default(),
+            before_class: Default::default(),
+            after_class: Default::default(),
+            placeholder_class: Default::default(),
+            on_reorder: None,
+            group: None,
+            on_receive: None,
+            on_remove: None,
+        }
+    }
+
+    /// Sets the axis the items are laid out along. Defaults to [`Orientation::Vertical`].
+    ///
+    /// With [`Orientation::Grid`], the items can wrap into rows, and the [`Sortable::container`]
+    /// also receives items dropped into the gaps between them, which go next to the closest item.
+    /// Right to left layouts are supported for both [`Orientation::Horizontal`] and grids.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[component]
+    /// fn Gallery<G: Html>(cx: Scope) -> View<G> {
+    ///     let photos = create_signal(cx, vec!["beach.jpg", "forest.jpg", "city.jpg"]);
+    ///     let sortable = create_sortable(cx, photos, |photo| *photo)
+    ///         .orientation(Orientation::Grid)
+    ///         .placeholder_class("photo-placeholder")
+    ///         .build();
+    ///     let container = sortable.container(cx);
+    ///
+    ///     view! { cx,
+    ///         div(style = "display:grid;grid-template-columns:repeat(4,1fr);", ref = container) {
+    ///             Keyed(
+    ///                 iterable = photos,
+    ///                 view = move |cx, photo| {
+    ///                     let node = sortable.item(cx, &photo);
+    ///                     view! { cx, img(src = photo, ref = node) }
+    ///                 },
+    ///                 key = |photo| *photo,
+    ///             )
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the backend used to drag the items, see [`DragBackend`].
+    pub fn backend(mut self, backend: DragBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// A class or list of classes to set on the item being dragged.
+    pub fn dragging_class(mut self, class: impl Into<String>) -> Self {
+        self.dragging_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the item the dragged item is hovering over.
+    pub fn hovering_class(mut self, class: impl Into<String>) -> Self {
+        self.hovering_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the hovered item when the dragged item would be
+    /// inserted before it.
+    pub fn before_class(mut self, class: impl Into<String>) -> Self {
+        self.before_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the hovered item when the dragged item would be
+    /// inserted after it.
+    pub fn after_class(mut self, class: impl Into<String>) -> Self {
+        self.after_class = class.into();
+        self
+    }
+
+    /// A class or list of classes for a placeholder element shown where the dragged item would be
+    /// inserted. The placeholder is only created if this is set, and needs to be styled to take up
+    /// the space of an item, e.g. a cell of a grid.
+    pub fn placeholder_class(mut self, class: impl Into<String>) -> Self {
+        self.placeholder_class = class.into();
+        self
+    }
+
+    /// Sets a callback to run after an item was moved. The arguments are the index the item was
+    /// moved from and the index it's at now.
+    pub fn on_reorder(mut self, f: impl Fn(usize, usize) + 'cx) -> Self {
+        self.on_reorder = Some(Box::new(f));
+        self
+    }
+
+    /// Join a [`SortableGroup`], so items can be moved between this list and the other lists in
+    /// the group. Moved items are removed from the signal of their old list and inserted into the
+    /// signal of the new one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore_dnd::*;
+    /// #[derive(Clone, PartialEq)]
+    /// struct Card {
+    ///     id: u32,
+    ///     title: String,
+    /// }
+    ///
+    /// #[component(inline_props)]
+    /// fn Column<'cx, G: Html>(
+    ///     cx: Scope<'cx>,
+    ///     cards: &'cx Signal<Vec<Card>>,
+    ///     group: &'cx SortableGroup<u32>,
+    /// ) -> View<G> {
+    ///     let sortable = create_sortable(cx, cards, |card| card.id)
+    ///         .group(group)
+    ///         .on_receive(|card, index| {
+    ///             log::info!("Received {} at {index}", card.title);
+    ///             Some(card)
+    ///         })
+    ///         .build();
+    ///     // The container receives cards dropped next to the cards, or into an empty column.
+    ///     let container = sortable.container(cx);
+    ///
+    ///     view! { cx,
+    ///         ul(class = "column", ref = container) {
+    ///             Keyed(
+    ///                 iterable = cards,
+    ///                 view = move |cx, card| {
+    ///                     let node = sortable.item(cx, &card);
+    ///                     view! { cx, li(ref = node) { (card.title) } }
+    ///                 },
+    ///                 key = |card| card.id,
+    ///             )
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[component]
+    /// fn Board<G: Html>(cx: Scope) -> View<G> {
+    ///     let group = create_sortable_group(cx);
+    ///     let todo = create_signal(cx, vec![Card { id: 0, title: "Write docs".to_string() }]);
+    ///     let done = create_signal(cx, Vec::new());
+    ///
+    ///     view! { cx,
+    ///         div(class = "board") {
+    ///             Column(cards = todo, group = group)
+    ///             Column(cards = done, group = group)
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn group(mut self, group: &'cx SortableGroup<K>) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Sets a callback to run when an item from another list in the group is dropped on this
+    /// list, with the item and the index it will be inserted at. Return the item to insert, which
+    /// can be a changed copy, or `None` to reject the move.
+    pub fn on_receive(mut self, f: impl Fn(T, usize) -> Option<T> + 'cx) -> Self {
+        self.on_receive = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback to run after an item was moved from this list to another list in the
+    /// group, with the item and the index it was removed from.
+    pub fn on_remove(mut self, f: impl Fn(T, usize) + 'cx) -> Self {
+        self.on_remove = Some(Box::new(f));
+        self
+    }
+
+    /// Create the sortable list. Use [`Sortable::item`] in the view of each item to make it
+    /// sortable.
+    pub fn build(self) -> &'cx Sortable<'cx, T, K> {
+        let scope = self.scope;
+        let sortable = Sortable {
+            id: next_id(),
+            options: self,
+            drop_index: create_signal(scope, None),
+            hovered: Default::default(),
+            elements: Default::default(),
+            placeholder: Default::default(),
+        };
+        // SAFETY: This is safe as long as the sortable has no custom `Drop` implementation
+        // See documentation for `create_ref_unsafe`.
+        let sortable = unsafe { create_ref_unsafe(scope, sortable) };
+
+        let context = use_dnd_context(scope);
+        create_effect(scope, move || {
+            if context.active_transfer().is_none() {
+                sortable.hovered.set(0);
+                set_if_changed(sortable.drop_index, None);
+            }
+        });
+        if !sortable.options.placeholder_class.is_empty() {
+            create_effect(scope, move || {
+                sortable.show_placeholder(*sortable.drop_index.get());
+            });
+        }
+        sortable
+    }
+}
+
+/// A sortable list created with [`create_sortable`].
+pub struct Sortable<'cx, T: 'static, K: 'static> {
+    id: u64,
+    options: SortableBuilder<'cx, T, K>,
+    drop_index: &'cx Signal<Option<usize>>,
+    /// The number of items and containers of the list the dragged item is over
+    hovered: Cell<usize>,
+    /// The elements of the mounted items
+    elements: RefCell<Vec<(K, Element)>>,
+    /// Created by the first item rendered in the browser, if there is a `placeholder_class`
+    placeholder: RefCell<Option<Element>>,
+}
+
+impl<'cx, T: Clone + 'static, K: PartialEq + Clone + 'static> Sortable<'cx, T, K> {
+    /// Make an item of the list draggable, and a drop target for the other items. Call this in
+    /// the scope of the item's view, i.e. in the `view` of a [`Keyed`]. Returns a [`NodeRef`] that
+    /// needs to be set as the item element's `ref` attribute.
+    pub fn item<'a, G: Html>(&'cx self, cx: Scope<'a>, item: &T) -> &'a NodeRef<G>
+    where
+        'cx: 'a,
+    {
+        let options = &self.options;
+        let key = (options.key_fn)(item);
+        if G::IS_BROWSER {
+            self.create_placeholder();
+        }
+
+        let node = create_draggable::<G>(cx)
+            .value_fn({
+                let (key, item) = (key.clone(), item.clone());
+                // `Keyed` keeps the view while the key stays the same, so look up the current
+                // version of the item.
+                move || SortableItem {
+                    list: self.id,
+                    group: options.group.map(|group| group.id),
+                    key: key.clone(),
+                    item: self.item_with_key(&key).unwrap_or_else(|| item.clone()),
+                }
+            })
+            .allowed_effect(DropEffect::Move)
+            .dragging_class(options.dragging_class.clone())
+            .backend(options.backend)
+            // The group records whether another list received the item, which is more reliable
+            // than the `dropEffect` the browser reports.
+            .on_drag_end({
+                let key = key.clone();
+                move |_| self.remove_moved(&key)
+            })
+            .build();
+
+        let droppable = create_droppable(cx)
+            .node_ref(node)
+            .orientation(options.orientation)
+            .on_drag_enter(move || self.enter())
+            .on_drag_leave(move || self.leave())
+            .hovering_class(options.hovering_class.clone())
+            .accept({
+                let key = key.clone();
+                move |dragged: &InMemory<SortableItem<T, K>>| {
+                    self.accepts(dragged) && (dragged.list != self.id || dragged.key != key)
+                }
+            })
+            .on_drop_at({
+                let key = key.clone();
+                move |dragged: InMemory<SortableItem<T, K>>, position| {
+                    self.drop_item(dragged.0, Some((&key, position.placement)))
+                }
+            });
+        let hover_position = droppable.hover_position();
+        let can_drop = droppable.can_drop();
+        let node = droppable.build();
+
+        // The elements are only needed to measure them while dragging, which can't happen on the
+        // server.
+        if G::IS_BROWSER {
+            create_effect(cx, {
+                let key = key.clone();
+                move || {
+                    if let Some(node) = node.try_get_raw() {
+                        let element: Element = node.to_web_sys().unchecked_into();
+                        let mut elements = self.elements.borrow_mut();
+                        elements.retain(|(existing, _)| *existing != key);
+                        elements.push((key.clone(), element));
+                    }
+                }
+            });
+            on_cleanup(cx, move || {
+                if let Some(node) = node.try_get_raw() {
+                    let node = node.to_web_sys();
+                    self.elements
+                        .borrow_mut()
+                        .retain(|(_, element)| !element.is_same_node(Some(&node)));
+                }
+            });
+        }
+
+        create_effect(cx, move || {
+            let Some(position) = *hover_position.get() else {
+                return;
+            };
+            if *can_drop.get() {
+                let after = position.placement == Placement::After;
+                let index = self.index_of(&key).map(|index| index + after as usize);
+                set_if_changed(self.drop_index, index);
+            }
+        });
+
+        if !options.before_class.is_empty() || !options.after_class.is_empty() {
+            create_effect(cx, move || {
+                let placement = hover_position
+                    .get()
+                    .filter(|_| *can_drop.get())
+                    .map(|position| position.placement);
+                if let Some(node) = node.try_get_raw() {
+                    for (placement_class, class) in [
+                        (Placement::Before, &options.before_class),
+                        (Placement::After, &options.after_class),
+                    ] {
+                        if placement == Some(placement_class) {
+                            node.add_class(class);
+                        } else {
+                            node.remove_class(class);
+                        }
+                    }
+                }
+            });
+        }
+        node
+    }
+
+    /// Make the element containing the items a drop target for items from other lists in the
+    /// group, which are inserted where they were last hovering, or at the end of the list. This is
+    /// needed to drop items into an empty list. Returns a [`NodeRef`] that needs to be set as the
+    /// container element's `ref` attribute.
+    pub fn container<'a, G: Html>(&'cx self, cx: Scope<'a>) -> &'a NodeRef<G>
+    where
+        'cx: 'a,
+    {
+        let grid = self.options.orientation == Orientation::Grid;
+        let droppable = create_droppable(cx)
+            .hovering_class(self.options.hovering_class.clone())
+            .accept(move |dragged: &InMemory<SortableItem<T, K>>| {
+                self.accepts(dragged) && (dragged.list != self.id || grid)
+            })
+            .on_drag_enter(move || self.enter())
+            .on_drag_leave(move || self.leave())
+            .on_drop(move |dragged: InMemory<SortableItem<T, K>>| self.drop_item(dragged.0, None));
+        let can_drop = droppable.can_drop();
+        let node: &NodeRef<G> = droppable.build();
+
+        if grid {
+            create_effect(cx, move || {
+                if let Some(node) = node.try_get_raw() {
+                    node.event(cx, ev::dragover, move |e: DragEvent| {
+                        // Items handle the events over them.
+                        if *can_drop.get_untracked() {
+                            let index =
+                                self.closest_index(e.client_x() as f64, e.client_y() as f64);
+                            set_if_changed(self.drop_index, Some(index));
+                        }
+                    });
+                }
+            });
+        }
+        node
+    }
+
+    /// A signal with the index the dragged item would be inserted at, counting the items of the
+    /// list before the move, or `None` if no item is dragged over the list.
+    pub fn drop_index(&self) -> &'cx ReadSignal<Option<usize>> {
+        self.drop_index
+    }
+
+    /// Track the dragged item entering an item or the container.
+    fn enter(&self) {
+        self.hovered.set(self.hovered.get() + 1);
+    }
+
+    /// Track the dragged item leaving an item or the container, and forget where it would be
+    /// dropped once it left the list. Items are entered before the previous one is left, so this
+    /// doesn't happen when moving between items.
+    fn leave(&self) {
+        self.hovered.set(self.hovered.get().saturating_sub(1));
+        if self.hovered.get() == 0 {
+            set_if_changed(self.drop_index, None);
+        }
+    }
+
+    /// Find the element of the item with the given key, if it's mounted.
+    fn element_of(&self, key: &K) -> Option<Element> {
+        self.elements
+            .borrow()
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, element)| element.clone())
+    }
+
+    /// Find the index an item dropped at a position in a grid goes to, next to the closest item.
+    fn closest_index(&self, client_x: f64, client_y: f64) -> usize {
+        let options = &self.options;
+        let items = options.items.get_untracked();
+        let closest = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let element = self.element_of(&(options.key_fn)(item))?;
+                let rect = element.get_bounding_client_rect();
+                let dx = (rect.left() - client_x)
+                    .max(client_x - rect.right())
+                    .max(0.0);
+                let dy = (rect.top() - client_y)
+                    .max(client_y - rect.bottom())
+                    .max(0.0);
+                Some((dx * dx + dy * dy, index, element, rect))
+            })
+            .min_by(|(a, ..), (b, ..)| a.total_cmp(b));
+
+        match closest {
+            Some((_, index, element, rect)) => {
+                match grid_placement(&rect.into(), client_x, client_y, is_rtl(&element)) {
+                    Placement::After => index + 1,
+                    Placement::Before | Placement::Inside => index,
+                }
+            }
+            None => items.len(),
+        }
+    }
+
+    /// Create the placeholder element, unless there already is one or it isn't needed.
+    fn create_placeholder(&self) {
+        let class = &self.options.placeholder_class;
+        if class.is_empty() || self.placeholder.borrow().is_some() {
+            return;
+        }
+        let Some(placeholder) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.create_element("div").ok())
+        else {
+            return;
+        };
+        placeholder.set_class_name(class);
+        // The placeholder moves while the item is over it, and browsers send no `dragleave` for
+        // removed elements, so it must not be the target of any drag events.
+        placeholder
+            .set_attribute("style", "pointer-events:none;")
+            .unwrap();
+        *self.placeholder.borrow_mut() = Some(placeholder);
+    }
+
+    /// Move the placeholder to the given index, or remove it.
+    fn show_placeholder(&self, index: Option<usize>) {
+        let Some(placeholder) = self.placeholder.borrow().clone() else {
+            return;
+        };
+        placeholder.remove();
+        let Some(index) = index else {
+            return;
+        };
+
+        let options = &self.options;
+        let items = options.items.get_untracked();
+        if let Some(element) = items
+            .get(index)
+            .and_then(|item| self.element_of(&(options.key_fn)(item)))
+        {
+            element.before_with_node_1(&placeholder).unwrap();
+        } else if let Some(element) = items
+            .last()
+            .and_then(|item| self.element_of(&(options.key_fn)(item)))
+        {
+            element.after_with_node_1(&placeholder).unwrap();
+        }
+    }
+
+    /// Whether a dragged item comes from this list or one in the same group.
+    fn accepts(&self, dragged: &SortableItem<T, K>) -> bool {
+        let group = self.options.group.map(|group| group.id);
+        dragged.list == self.id || (group.is_some() && dragged.group == group)
+    }
+
+    /// Find the current item with the given key.
+    fn item_with_key(&self, key: &K) -> Option<T> {
+        let options = &self.options;
+        options
+            .items
+            .get_untracked()
+            .iter()
+            .find(|item| (options.key_fn)(item) == *key)
+            .cloned()
+    }
+
+    /// Find the index of the item with the given key.
+    fn index_of(&self, key: &K) -> Option<usize> {
+        let options = &self.options;
+        options
+            .items
+            .get_untracked()
+            .iter()
+            .position(|item| (options.key_fn)(item) == *key)
+    }
+
+    /// Handle an item dropped before or after the item with the given key, or on the container if
+    /// there is none.
+    fn drop_item(
+        &self,
+        dragged: SortableItem<T, K>,
+        target: Option<(&K, Placement)>,
+    ) -> Result<(), &'static str> {
+        // Remove the placeholder before the list changes, so it doesn't get in the way of
+        // rendering the items.
+        let drop_index = self.drop_index.get_untracked().as_ref().to_owned();
+        set_if_changed(self.drop_index, None);
+
+        let insert = match target {
+            Some((key, placement)) => {
+                let Some(index) = self.index_of(key) else {
+                    return Ok(());
+                };
+                match placement {
+                    Placement::After => index + 1,
+                    Placement::Before | Placement::Inside => index,
+                }
+            }
+            None => drop_index.unwrap_or_else(|| self.options.items.get_untracked().len()),
+        };
+
+        if dragged.list == self.id {
+            self.move_item(&dragged.key, insert);
+            Ok(())
+        } else {
+            self.receive_item(dragged, insert)
+        }
+    }
+
+    /// Insert an item from another list in the group.
+    fn receive_item(&self, dragged: SortableItem<T, K>, index: usize) -> Result<(), &'static str> {
+        let options = &self.options;
+        let item = match options.on_receive.as_ref() {
+            Some(on_receive) => on_receive(dragged.item, index).ok_or("The move was rejected")?,
+            None => dragged.item,
+        };
+        log::trace!("Receiving item at {index}");
+        options.items.modify().insert(index, item);
+        if let Some(group) = options.group {
+            *group.moved.borrow_mut() = Some((dragged.list, dragged.key));
+        }
+        Ok(())
+    }
+
+    /// Remove an item that was moved to another list in the group, once its drag ended. Clears
+    /// the group's record of the move either way.
+    fn remove_moved(&'cx self, key: &K) {
+        let Some(group) = self.options.group else {
+            return;
+        };
+        let moved = group.moved.take();
+        if moved.as_ref() != Some(&(self.id, key.clone())) {
+            return;
+        }
+
+        // Removing the item disposes the scope of its draggable, so wait until its `dragend`
+        // handler returned.
+        let key = key.clone();
+        spawn_local_scoped(self.options.scope, async move {
+            let Some(index) = self.index_of(&key) else {
+                return;
+            };
+            log::trace!("Removing moved item from {index}");
+            let item = self.options.items.modify().remove(index);
+            if let Some(on_remove) = self.options.on_remove.as_ref() {
+                on_remove(item, index);
+            }
+        });
+    }
+
+    /// Move the item with the given key in this list so it's inserted at the index `insert` of the
+    /// list before the move.
+    fn move_item(&self, key: &K, insert: usize) {
+        let options = &self.options;
+        let Some((from, to)) = self
+            .index_of(key)
+            .and_then(|from| moved_index(from, insert))
+        else {
+            return;
+        };
+
+        log::trace!("Moving item from {from} to {to}");
+        {
+            let mut items = options.items.modify();
+            let item = items.remove(from);
+            items.insert(to, item);
+        }
+        if let Some(on_reorder) = options.on_reorder.as_ref() {
+            on_reorder(from, to);
+        }
+    }
+}
+
+/// Find the index an item at `from` ends up at when it's inserted at the index `insert` of the list
+/// before the move. Returns `None` if the item stays where it is.
+fn moved_index(from: usize, insert: usize) -> Option<(usize, usize)> {
+    // The indices after the dragged item shift by one once it's removed.
+    let to = if from < insert { insert - 1 } else { insert };
+    (from != to).then_some((from, to))
+}
+
+/// Create a sortable list from a signal of items. Items are identified by the key returned by
+/// `key_fn`, like in [`Keyed`]. Dropping an item before or after another one moves it there,
+/// and updates the signal. The [`SortableBuilder`] can be used to further configure the list.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[derive(Clone, PartialEq)]
+/// struct Task {
+///     id: u32,
+///     title: String,
+/// }
+///
+/// #[component]
+/// fn TaskList<G: Html>(cx: Scope) -> View<G> {
+///     let tasks = create_signal(cx, vec![
+///         Task { id: 0, title: "Write docs".to_string() },
+///         Task { id: 1, title: "Release".to_string() },
+///     ]);
+///     let sortable = create_sortable(cx, tasks, |task| task.id)
+///         .dragging_class("dragging")
+///         .before_class("drop-before")
+///         .after_class("drop-after")
+///         .on_reorder(|from, to| log::info!("Moved task from {from} to {to}"))
+///         .build();
+///
+///     view! { cx,
+///         ul {
+///             Keyed(
+///                 iterable = tasks,
+///                 view = move |cx, task| {
+///                     let node = sortable.item(cx, &task);
+///                     view! { cx, li(ref = node) { (task.title) } }
+///                 },
+///                 key = |task| task.id,
+///             )
+///         }
+///     }
+/// }
+/// ```
+pub fn create_sortable<'cx, T: Clone + 'static, K: PartialEq + Clone + 'static>(
+    cx: Scope<'cx>,
+    items: &'cx Signal<Vec<T>>,
+    key_fn: impl Fn(&T) -> K + 'cx,
+) -> SortableBuilder<'cx, T, K> {
+    SortableBuilder::new(cx, items, key_fn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sycamore::render_to_string;
+
+    /// Move an item like `Sortable::move_item` does.
+    fn move_in(items: &mut Vec<char>, from: usize, insert: usize) {
+        if let Some((from, to)) = moved_index(from, insert) {
+            let item = items.remove(from);
+            items.insert(to, item);
+        }
+    }
+
+    #[test]
+    fn moves_down() {
+        let mut items = vec!['a', 'b', 'c', 'd'];
+        // Dropped after `c`
+        move_in(&mut items, 0, 3);
+        assert_eq!(items, ['b', 'c', 'a', 'd']);
+        // Dropped after the last item
+        move_in(&mut items, 0, 4);
+        assert_eq!(items, ['c', 'a', 'd', 'b']);
+    }
+
+    #[test]
+    fn moves_up() {
+        let mut items = vec!['a', 'b', 'c', 'd'];
+        move_in(&mut items, 3, 0);
+        assert_eq!(items, ['d', 'a', 'b', 'c']);
+        move_in(&mut items, 2, 1);
+        assert_eq!(items, ['d', 'b', 'a', 'c']);
+    }
+
+    #[test]
+    fn drops_next_to_itself() {
+        // Before itself, or after itself, i.e. before the next item
+        assert_eq!(moved_index(1, 1), None);
+        assert_eq!(moved_index(1, 2), None);
+        assert_eq!(moved_index(1, 3), Some((1, 2)));
+        assert_eq!(moved_index(1, 0), Some((1, 0)));
+    }
+
+    #[test]
+    fn renders_on_the_server() {
+        let html = render_to_string(|cx| {
+            let items = create_signal(cx, vec![1, 2, 3]);
+            let sortable = create_sortable(cx, items, |item| *item)
+                .placeholder_class("placeholder")
+                .build();
+            view! { cx,
+                ul(ref = sortable.container(cx)) {
+                    Keyed(
+                        iterable = items,
+                        view = move |cx, item| {
+                            let node = sortable.item(cx, &item);
+                            view! { cx, li(ref = node) { (item) } }
+                        },
+                        key = |item| *item,
+                    )
+                }
+            }
+        });
+        assert_eq!(html.matches("<li").count(), 3);
+    }
+}
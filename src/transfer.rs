@@ -0,0 +1,94 @@
+use crate::{transfer_tag, AsTransfer};
+use serde::Serialize;
+use web_sys::DataTransfer;
+
+/// Several representations of an item, written to the [`DataTransfer`] at once.
+///
+/// Droppables in the app read the typed JSON, while other applications like text editors or email
+/// clients pick the format they understand best.
+///
+/// # Example
+///
+/// ```
+/// # use serde::Serialize;
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[derive(Serialize)]
+/// struct Card {
+///     title: String,
+///     url: String,
+/// }
+///
+/// #[component(inline_props)]
+/// fn CardItem<G: Html>(cx: Scope, card: Card) -> View<G> {
+///     let title = card.title.clone();
+///     let drag = create_draggable(cx)
+///         .data(
+///             TransferSet::new()
+///                 .json(&card)
+///                 .text(&card.title)
+///                 .uri_list([&card.url])
+///                 .html(format!("<a href=\"{}\">{}</a>", card.url, card.title)),
+///         )
+///         .build();
+///
+///     view! { cx,
+///         div(class = "card", ref = drag) {
+///             (title)
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct TransferSet {
+    entries: Vec<(String, String)>,
+}
+
+impl TransferSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add data with an arbitrary format. Setting a format twice replaces the earlier data.
+    pub fn with(mut self, format: impl Into<String>, data: impl Into<String>) -> Self {
+        let format = format.into();
+        self.entries.retain(|(existing, _)| *existing != format);
+        self.entries.push((format, data.into()));
+        self
+    }
+
+    /// Add the value as JSON, the same way [`DraggableBuilder::data`](crate::DraggableBuilder::data)
+    /// writes it, so typed droppables can read it.
+    pub fn json<T: Serialize + ?Sized>(self, value: &T) -> Self {
+        self.with("data/json", serde_json::to_string(value).unwrap())
+            .with(transfer_tag::<T>(), "")
+    }
+
+    /// Add a plain text representation (`text/plain`).
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.with("text/plain", text)
+    }
+
+    /// Add a list of URIs (`text/uri-list`).
+    pub fn uri_list<S: AsRef<str>>(self, uris: impl IntoIterator<Item = S>) -> Self {
+        let uris = uris
+            .into_iter()
+            .map(|uri| uri.as_ref().to_string())
+            .collect::<Vec<_>>();
+        self.with("text/uri-list", uris.join("\r\n"))
+    }
+
+    /// Add an HTML representation (`text/html`).
+    pub fn html(self, html: impl Into<String>) -> Self {
+        self.with("text/html", html)
+    }
+}
+
+impl AsTransfer for TransferSet {
+    fn write_to_transfer(&self, transfer: &DataTransfer) {
+        for (format, data) in &self.entries {
+            transfer.set_data(format, data).unwrap();
+        }
+    }
+}
@@ -0,0 +1,811 @@
+use crate::{
+    create_draggable, create_droppable, next_id, position::is_rtl, set_if_changed, use_dnd_context,
+    DragBackend, DropEffect, DropPosition, InMemory, Placement,
+};
+use std::cell::{Cell, Ref, RefCell};
+use sycamore::{futures::spawn_local_scoped, prelude::*, web::html::ev};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{DragEvent, Element};
+
+/// A move of a node in a tree, passed to [`TreeBuilder::on_move`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TreeMove<K> {
+    /// The key of the moved node
+    pub node: K,
+    /// The key of the node it's moved into, or `None` to move it to the root of the tree
+    pub new_parent: Option<K>,
+    /// The index among the children of `new_parent` the node ends up at, i.e. counting the
+    /// children without the moved node
+    pub index: usize,
+}
+
+/// The in-memory value of a node dragged in a tree.
+#[derive(Clone)]
+struct TreeItem<K> {
+    tree: u64,
+    key: K,
+}
+
+/// The builder for the [`create_tree`] options
+pub struct TreeBuilder<'cx, K: 'static> {
+    scope: Scope<'cx>,
+    #[allow(clippy::type_complexity)]
+    parent_fn: Box<dyn Fn(&K) -> Option<K> + 'cx>,
+    #[allow(clippy::type_complexity)]
+    children_fn: Box<dyn Fn(Option<&K>) -> Vec<K> + 'cx>,
+    backend: DragBackend,
+    dragging_class: String,
+    hovering_class: String,
+    before_class: String,
+    inside_class: String,
+    after_class: String,
+    max_depth: Option<usize>,
+    indent: Option<f64>,
+    expand_delay: u32,
+    #[allow(clippy::type_complexity)]
+    on_expand: Option<Box<dyn Fn(&K) + 'cx>>,
+    on_move: Option<Box<dyn Fn(TreeMove<K>) + 'cx>>,
+}
+
+impl<'cx, K: PartialEq + Clone + 'static> TreeBuilder<'cx, K> {
+    fn new(
+        scope: Scope<'cx>,
+        parent_fn: impl Fn(&K) -> Option<K> + 'cx,
+        children_fn: impl Fn(Option<&K>) -> Vec<K> + 'cx,
+    ) -> Self {
+        Self {
+            scope,
+            parent_fn: Box::new(parent_fn),
+            children_fn: Box::new(children_fn),
+            backend: Default::default(),
+            dragging_class: Default::default(),
+            hovering_class: Default::default(),
+            before_class: Default::default(),
+            inside_class: Default::default(),
+            after_class: Default::default(),
+            max_depth: None,
+            indent: None,
+            expand_delay: 500,
+            on_expand: None,
+            on_move: None,
+        }
+    }
+
+    /// Sets the backend used to drag the nodes, see [`DragBackend`].
+    pub fn backend(mut self, backend: DragBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// A class or list of classes to set on the node being dragged.
+    pub fn dragging_class(mut self, class: impl Into<String>) -> Self {
+        self.dragging_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the node the dragged node is hovering over.
+    pub fn hovering_class(mut self, class: impl Into<String>) -> Self {
+        self.hovering_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the hovered node when the dragged node would be
+    /// inserted before it.
+    pub fn before_class(mut self, class: impl Into<String>) -> Self {
+        self.before_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the hovered node when the dragged node would be
+    /// moved inside it.
+    pub fn inside_class(mut self, class: impl Into<String>) -> Self {
+        self.inside_class = class.into();
+        self
+    }
+
+    /// A class or list of classes to set on the hovered node when the dragged node would be
+    /// inserted after it.
+    pub fn after_class(mut self, class: impl Into<String>) -> Self {
+        self.after_class = class.into();
+        self
+    }
+
+    /// The deepest level nodes can be moved to, where nodes at the root of the tree have depth
+    /// `0`. This includes the children of the moved node, so a node with grandchildren can't be
+    /// moved deeper than `max_depth - 2`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// The width in pixels each level of the tree is indented by. When set, moving the pointer
+    /// sideways while dragging changes the level of a node dropped after another one: each
+    /// `indent` to the right nests it a level deeper, up to inside the node above, and each
+    /// `indent` to the left moves it out of one of the node's parents, as long as the node above
+    /// is the last one in them. Right to left layouts are flipped.
+    pub fn indent(mut self, pixels: f64) -> Self {
+        self.indent = Some(pixels);
+        self
+    }
+
+    /// Sets a callback to run when a dragged node hovers inside a collapsed node, which should
+    /// expand it. A node counts as collapsed when it has children but none of them are rendered.
+    pub fn on_expand(mut self, f: impl Fn(&K) + 'cx) -> Self {
+        self.on_expand = Some(Box::new(f));
+        self
+    }
+
+    /// How long a dragged node needs to hover inside a collapsed node before it's expanded, in
+    /// milliseconds. Defaults to 500.
+    pub fn expand_delay(mut self, millis: u32) -> Self {
+        self.expand_delay = millis;
+        self
+    }
+
+    /// Sets a callback to run when a node is dropped, with the move to apply to the tree. The
+    /// tree doesn't change the data itself, so this needs to update it. Moves into the node's own
+    /// children or deeper than [`TreeBuilder::max_depth`] are rejected before this is called.
+    pub fn on_move(mut self, f: impl Fn(TreeMove<K>) + 'cx) -> Self {
+        self.on_move = Some(Box::new(f));
+        self
+    }
+
+    /// Create the tree. Use [`Tree::node`] in the view of each node to make it draggable.
+    pub fn build(self) -> &'cx Tree<'cx, K> {
+        let scope = self.scope;
+        let tree = Tree {
+            id: next_id(),
+            options: self,
+            mounted: Default::default(),
+            dragging: create_signal(scope, None),
+            projected: create_signal(scope, None),
+            expanding: Default::default(),
+            snapshot: Default::default(),
+            drag_start_x: Default::default(),
+            rtl: Default::default(),
+        };
+        // SAFETY: This is safe as long as the tree has no custom `Drop` implementation
+        // See documentation for `create_ref_unsafe`.
+        let tree = unsafe { create_ref_unsafe(scope, tree) };
+
+        let context = use_dnd_context(scope);
+        create_effect(scope, move || {
+            if context.active_transfer().is_none() {
+                set_if_changed(tree.projected, None);
+                tree.expanding.take();
+                tree.snapshot.take();
+            }
+        });
+        tree
+    }
+}
+
+/// A tree created with [`create_tree`].
+pub struct Tree<'cx, K: 'static> {
+    id: u64,
+    options: TreeBuilder<'cx, K>,
+    /// The keys of the rendered nodes
+    mounted: RefCell<Vec<K>>,
+    dragging: &'cx Signal<Option<K>>,
+    projected: &'cx Signal<Option<TreeMove<K>>>,
+    /// The node waiting to be expanded
+    expanding: RefCell<Option<K>>,
+    /// The structure of the tree when the current drag started
+    snapshot: RefCell<Option<TreeSnapshot<K>>>,
+    /// The horizontal position of the pointer when the current drag started
+    drag_start_x: Cell<f64>,
+    /// Whether the node dragged in the current drag is laid out right to left
+    rtl: Cell<bool>,
+}
+
+impl<'cx, K: PartialEq + Clone + 'static> Tree<'cx, K> {
+    /// Make a node of the tree draggable, and a drop target for the other nodes. Call this in the
+    /// scope of the node's view. Returns a [`NodeRef`] that needs to be set as the `ref`
+    /// attribute of the node's row, which shouldn't contain the rows of its children.
+    pub fn node<'a, G: Html>(&'cx self, cx: Scope<'a>, key: K) -> &'a NodeRef<G>
+    where
+        'cx: 'a,
+    {
+        let options = &self.options;
+        self.mounted.borrow_mut().push(key.clone());
+        on_cleanup(cx, {
+            let key = key.clone();
+            move || {
+                let mut mounted = self.mounted.borrow_mut();
+                if let Some(index) = mounted.iter().position(|mounted| *mounted == key) {
+                    mounted.remove(index);
+                }
+            }
+        });
+
+        let node = create_draggable::<G>(cx)
+            .value(TreeItem {
+                tree: self.id,
+                key: key.clone(),
+            })
+            .allowed_effect(DropEffect::Move)
+            .dragging_class(options.dragging_class.clone())
+            .backend(options.backend)
+            .on_drag_start({
+                let key = key.clone();
+                move || self.dragging.set(Some(key.clone()))
+            })
+            .on_drag_end(move |_| self.dragging.set(None))
+            .build();
+
+        let droppable = create_droppable(cx)
+            .node_ref(node)
+            .placement_thresholds(0.25, 0.75)
+            .hovering_class(options.hovering_class.clone())
+            .accept({
+                let key = key.clone();
+                move |dragged: &InMemory<TreeItem<K>>| {
+                    dragged.tree == self.id
+                        && self
+                            .snapshot(&dragged.key)
+                            .map(|snapshot| !snapshot.is_within_dragged(&key))
+                            .unwrap_or(false)
+                }
+            })
+            .on_drop_at({
+                let key = key.clone();
+                move |dragged: InMemory<TreeItem<K>>, position| {
+                    set_if_changed(self.projected, None);
+                    let Some(tree_move) = self.resolve(&dragged.0.key, &key, &position)? else {
+                        return Ok(());
+                    };
+                    log::trace!("Moving tree node to {}", tree_move.index);
+                    if let Some(on_move) = options.on_move.as_ref() {
+                        on_move(tree_move);
+                    }
+                    Ok::<_, &str>(())
+                }
+            });
+        let hover_position = droppable.hover_position();
+        let can_drop = droppable.can_drop();
+        let node = droppable.build();
+
+        if G::IS_BROWSER {
+            create_effect(cx, move || {
+                if let Some(node) = node.try_get_raw() {
+                    let element: Element = node.to_web_sys().unchecked_into();
+                    node.event(cx, ev::dragstart, move |e: DragEvent| {
+                        self.drag_start_x.set(e.client_x() as f64);
+                        self.rtl.set(is_rtl(&element));
+                    });
+                }
+            });
+        }
+
+        create_effect(cx, move || {
+            let hovering = hover_position.get().is_some();
+            let position = (*hover_position.get()).filter(|_| *can_drop.get());
+            let dragged = self.dragging.get_untracked();
+            let tree_move =
+                position
+                    .zip(dragged.as_ref().as_ref())
+                    .and_then(|(position, dragged)| {
+                        self.resolve(dragged, &key, &position).ok().flatten()
+                    });
+            let placement = position
+                .filter(|_| tree_move.is_some())
+                .map(|position| position.placement);
+
+            if let Some(node) = node.try_get_raw() {
+                for (placement_class, class) in [
+                    (Placement::Before, &options.before_class),
+                    (Placement::Inside, &options.inside_class),
+                    (Placement::After, &options.after_class),
+                ] {
+                    if placement == Some(placement_class) {
+                        node.add_class(class);
+                    } else {
+                        node.remove_class(class);
+                    }
+                }
+            }
+            if hovering {
+                set_if_changed(self.projected, tree_move);
+            }
+
+            if placement == Some(Placement::Inside) && self.is_collapsed(&key) {
+                self.expand_later(cx, &key);
+            } else if self.expanding.borrow().as_ref() == Some(&key) {
+                self.expanding.take();
+            }
+        });
+        node
+    }
+
+    /// A signal with the move that would be made if the dragged node was dropped where it's
+    /// hovering, or `None` if it can't be dropped there. This can be used to show an indicator
+    /// at the level the node would end up at.
+    pub fn projected_move(&self) -> &'cx ReadSignal<Option<TreeMove<K>>> {
+        self.projected
+    }
+
+    /// The depth of a node in the tree, where nodes at the root have depth `0`.
+    pub fn depth(&self, key: &K) -> usize {
+        let mut depth = 0;
+        let mut current = (self.options.parent_fn)(key);
+        while let Some(parent) = current {
+            depth += 1;
+            current = (self.options.parent_fn)(&parent);
+        }
+        depth
+    }
+
+    /// The structure of the tree for a drag of the node `dragged`, which is only read once per
+    /// drag. Returns `None` if the node isn't in the tree.
+    fn snapshot(&self, dragged: &K) -> Option<Ref<'_, TreeSnapshot<K>>> {
+        let outdated = self
+            .snapshot
+            .borrow()
+            .as_ref()
+            .map(|snapshot| snapshot.dragged().key != *dragged)
+            .unwrap_or(true);
+        if outdated {
+            *self.snapshot.borrow_mut() = TreeSnapshot::new(dragged, &*self.options.children_fn);
+        }
+        Ref::filter_map(self.snapshot.borrow(), Option::as_ref).ok()
+    }
+
+    /// Whether any children of a node are rendered.
+    fn is_expanded(&self, key: &K) -> bool {
+        let mounted = self.mounted.borrow();
+        (self.options.children_fn)(Some(key))
+            .iter()
+            .any(|child| mounted.contains(child))
+    }
+
+    /// Whether a node has children, but none of them are rendered.
+    fn is_collapsed(&self, key: &K) -> bool {
+        !(self.options.children_fn)(Some(key)).is_empty() && !self.is_expanded(key)
+    }
+
+    /// Call `on_expand` for a node once the dragged node hovered inside it for the expand delay.
+    fn expand_later<'a>(&'cx self, cx: Scope<'a>, key: &K)
+    where
+        'cx: 'a,
+    {
+        if self.options.on_expand.is_none() || self.expanding.borrow().as_ref() == Some(key) {
+            return;
+        }
+        *self.expanding.borrow_mut() = Some(key.clone());
+
+        let key = key.clone();
+        let delay = self.options.expand_delay as i32;
+        spawn_local_scoped(cx, async move {
+            let timeout = js_sys::Promise::new(&mut |resolve, _| {
+                web_sys::window()
+                    .unwrap()
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, delay)
+                    .unwrap();
+            });
+            let _ = JsFuture::from(timeout).await;
+            if self.expanding.borrow().as_ref() != Some(&key) {
+                return;
+            }
+            self.expanding.take();
+            log::trace!("Expanding tree node");
+            if let Some(on_expand) = self.options.on_expand.as_ref() {
+                on_expand(&key);
+            }
+        });
+    }
+
+    /// Find where the node `dragged` goes when it's dropped at a position over the node `target`.
+    /// Returns `None` if the node wouldn't move.
+    fn resolve(
+        &self,
+        dragged: &K,
+        target: &K,
+        position: &DropPosition,
+    ) -> Result<Option<TreeMove<K>>, &'static str> {
+        let snapshot = self
+            .snapshot(dragged)
+            .ok_or("The dragged node isn't in the tree")?;
+        // The children of an expanded node are rendered right after it.
+        let expanded = position.placement == Placement::After && self.is_expanded(target);
+        let offset = position.client_x - self.drag_start_x.get();
+        snapshot.resolve(
+            target,
+            position.placement,
+            if self.rtl.get() { -offset } else { offset },
+            expanded,
+            self.options.indent,
+            self.options.max_depth,
+        )
+    }
+}
+
+/// A node of a [`TreeSnapshot`].
+struct SnapshotNode<K> {
+    key: K,
+    /// The index of the parent in the snapshot
+    parent: Option<usize>,
+    /// The index among its siblings
+    index: usize,
+    depth: usize,
+    /// The number of children
+    children: usize,
+    /// Whether it's the last of its siblings, not counting the dragged node
+    is_last: bool,
+    /// Whether it's the dragged node or one of its descendants
+    within_dragged: bool,
+}
+
+/// The structure of a tree when a drag started, so hovering doesn't need to walk the tree on
+/// every `dragover`.
+struct TreeSnapshot<K> {
+    /// The nodes in depth-first order
+    nodes: Vec<SnapshotNode<K>>,
+    /// The index of the dragged node
+    dragged: usize,
+    /// The number of levels below the dragged node
+    height: usize,
+}
+
+impl<K: PartialEq + Clone> TreeSnapshot<K> {
+    /// Read the tree from `children_fn`. Returns `None` if `dragged` isn't in the tree.
+    fn new(dragged: &K, children_fn: &dyn Fn(Option<&K>) -> Vec<K>) -> Option<Self> {
+        let mut snapshot = Self {
+            nodes: Vec::new(),
+            dragged: usize::MAX,
+            height: 0,
+        };
+        snapshot.add_children(None, dragged, children_fn);
+        let depth = snapshot.nodes.get(snapshot.dragged)?.depth;
+        snapshot.height = snapshot
+            .nodes
+            .iter()
+            .filter(|node| node.within_dragged)
+            .map(|node| node.depth - depth)
+            .max()
+            .unwrap_or(0);
+        Some(snapshot)
+    }
+
+    fn add_children(
+        &mut self,
+        parent: Option<usize>,
+        dragged: &K,
+        children_fn: &dyn Fn(Option<&K>) -> Vec<K>,
+    ) {
+        let (depth, within_dragged) = match parent {
+            Some(parent) => {
+                let parent = &self.nodes[parent];
+                (parent.depth + 1, parent.within_dragged)
+            }
+            None => (0, false),
+        };
+        let children = children_fn(parent.map(|parent| &self.nodes[parent].key));
+        if let Some(parent) = parent {
+            self.nodes[parent].children = children.len();
+        }
+        let last = children.iter().rposition(|child| child != dragged);
+        for (index, key) in children.into_iter().enumerate() {
+            let is_dragged = key == *dragged;
+            if is_dragged {
+                self.dragged = self.nodes.len();
+            }
+            self.nodes.push(SnapshotNode {
+                key,
+                parent,
+                index,
+                depth,
+                children: 0,
+                is_last: last == Some(index),
+                within_dragged: within_dragged || is_dragged,
+            });
+            self.add_children(Some(self.nodes.len() - 1), dragged, children_fn);
+        }
+    }
+
+    fn dragged(&self) -> &SnapshotNode<K> {
+        &self.nodes[self.dragged]
+    }
+
+    /// Whether the node `key` is the dragged node or one of its descendants.
+    fn is_within_dragged(&self, key: &K) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| node.within_dragged && node.key == *key)
+    }
+
+    /// Find where the dragged node goes when it's dropped over the node `target`, with the
+    /// pointer moved `offset` pixels towards the end of the line since the drag started.
+    /// `expanded` is whether the children of `target` are rendered right after it.
+    fn resolve(
+        &self,
+        target: &K,
+        placement: Placement,
+        offset: f64,
+        expanded: bool,
+        indent: Option<f64>,
+        max_depth: Option<usize>,
+    ) -> Result<Option<TreeMove<K>>, &'static str> {
+        let target = self
+            .nodes
+            .iter()
+            .position(|node| node.key == *target)
+            .ok_or("The target node isn't in the tree")?;
+        let node = &self.nodes[target];
+        if node.within_dragged {
+            return Err("A node can't be moved into itself");
+        }
+        let (new_parent, index) = match placement {
+            Placement::Before => (node.parent, node.index),
+            Placement::Inside => (Some(target), node.children),
+            Placement::After if expanded => (Some(target), 0),
+            Placement::After => match indent.filter(|indent| *indent > 0.0) {
+                Some(indent) => {
+                    let levels = (offset / indent).round();
+                    let level = (self.dragged().depth as f64 + levels).max(0.0) as usize;
+                    if level > node.depth {
+                        (Some(target), node.children)
+                    } else {
+                        // Only move out of parents that end with the target.
+                        let mut current = target;
+                        while self.nodes[current].depth > level && self.nodes[current].is_last {
+                            current = self.nodes[current].parent.unwrap();
+                        }
+                        let current = &self.nodes[current];
+                        (current.parent, current.index + 1)
+                    }
+                }
+                None => (node.parent, node.index + 1),
+            },
+        };
+
+        let depth = new_parent
+            .map(|parent| self.nodes[parent].depth + 1)
+            .unwrap_or(0);
+        if let Some(max_depth) = max_depth {
+            if depth + self.height > max_depth {
+                return Err("The node would be nested too deep");
+            }
+        }
+
+        // The indices after the dragged node shift by one once it's removed.
+        let dragged = self.dragged();
+        let mut index = index;
+        if dragged.parent == new_parent {
+            if dragged.index < index {
+                index -= 1;
+            }
+            if dragged.index == index {
+                return Ok(None);
+            }
+        }
+        Ok(Some(TreeMove {
+            node: dragged.key.clone(),
+            new_parent: new_parent.map(|parent| self.nodes[parent].key.clone()),
+            index,
+        }))
+    }
+}
+
+/// Create a tree whose nodes can be dragged before, after, or inside each other. The tree is
+/// described by `parent_fn`, which returns the parent of a node, and `children_fn`, which returns
+/// the children of a node in order, or the nodes at the root for `None`. Nodes are identified by
+/// their key, and dropping one calls [`TreeBuilder::on_move`] with the resulting [`TreeMove`].
+///
+/// Nodes can be rendered as a flat list indented by their [`Tree::depth`], or nested, as long as
+/// the `ref` of a node is set on its own row.
+///
+/// # Example
+///
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_dnd::*;
+/// #[derive(Clone, PartialEq)]
+/// struct Folder {
+///     id: u32,
+///     parent: Option<u32>,
+///     name: String,
+/// }
+///
+/// #[component]
+/// fn Folders<G: Html>(cx: Scope) -> View<G> {
+///     let folders = create_signal(cx, vec![
+///         Folder { id: 0, parent: None, name: "Documents".to_string() },
+///         Folder { id: 1, parent: Some(0), name: "Invoices".to_string() },
+///         Folder { id: 2, parent: None, name: "Photos".to_string() },
+///     ]);
+///     let tree = create_tree(
+///         cx,
+///         |id| folders.get_untracked().iter().find(|f| f.id == *id).and_then(|f| f.parent),
+///         |parent| {
+///             let folders = folders.get_untracked();
+///             folders.iter().filter(|f| f.parent.as_ref() == parent).map(|f| f.id).collect()
+///         },
+///     )
+///     .max_depth(3)
+///     .indent(20.0)
+///     .before_class("drop-before")
+///     .inside_class("drop-inside")
+///     .after_class("drop-after")
+///     .on_move(|m| log::info!("Moving {} into {:?} at {}", m.node, m.new_parent, m.index))
+///     .build();
+///
+///     view! { cx,
+///         ul {
+///             Keyed(
+///                 iterable = folders,
+///                 view = move |cx, folder| {
+///                     let node = tree.node(cx, folder.id);
+///                     let style = format!("padding-left:{}px;", tree.depth(&folder.id) * 20);
+///                     view! { cx, li(style = style, ref = node) { (folder.name) } }
+///                 },
+///                 key = |folder| folder.id,
+///             )
+///         }
+///     }
+/// }
+/// ```
+pub fn create_tree<'cx, K: PartialEq + Clone + 'static>(
+    cx: Scope<'cx>,
+    parent_fn: impl Fn(&K) -> Option<K> + 'cx,
+    children_fn: impl Fn(Option<&K>) -> Vec<K> + 'cx,
+) -> TreeBuilder<'cx, K> {
+    TreeBuilder::new(cx, parent_fn, children_fn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sycamore::render_to_string;
+
+    /// ```text
+    /// 0
+    ///   1
+    ///   2
+    ///     3
+    /// 4
+    /// 5
+    /// ```
+    const NODES: [(u32, Option<u32>); 6] = [
+        (0, None),
+        (1, Some(0)),
+        (2, Some(0)),
+        (3, Some(2)),
+        (4, None),
+        (5, None),
+    ];
+
+    fn snapshot(dragged: u32) -> TreeSnapshot<u32> {
+        let children = |parent: Option<&u32>| {
+            NODES
+                .iter()
+                .filter(|(_, node_parent)| node_parent.as_ref() == parent)
+                .map(|(node, _)| *node)
+                .collect()
+        };
+        TreeSnapshot::new(&dragged, &children).unwrap()
+    }
+
+    fn tree_move(node: u32, new_parent: Option<u32>, index: usize) -> Option<TreeMove<u32>> {
+        Some(TreeMove {
+            node,
+            new_parent,
+            index,
+        })
+    }
+
+    #[test]
+    fn snapshot_structure() {
+        let tree = snapshot(0);
+        assert_eq!(tree.height, 2);
+        assert!(tree.is_within_dragged(&3));
+        assert!(!tree.is_within_dragged(&4));
+        assert!(TreeSnapshot::new(&9, &|_: Option<&u32>| Vec::new()).is_none());
+    }
+
+    #[test]
+    fn placements() {
+        let resolve = |dragged, target, placement| {
+            snapshot(dragged).resolve(&target, placement, 0.0, false, None, None)
+        };
+        assert_eq!(resolve(5, 0, Placement::Before), Ok(tree_move(5, None, 0)));
+        assert_eq!(resolve(0, 4, Placement::After), Ok(tree_move(0, None, 1)));
+        assert_eq!(
+            resolve(5, 1, Placement::Inside),
+            Ok(tree_move(5, Some(1), 0))
+        );
+        assert_eq!(
+            resolve(1, 0, Placement::Inside),
+            Ok(tree_move(1, Some(0), 1))
+        );
+        // Dropping a node where it already is doesn't move it.
+        assert_eq!(resolve(4, 0, Placement::After), Ok(None));
+        assert_eq!(resolve(4, 5, Placement::Before), Ok(None));
+        assert!(resolve(0, 3, Placement::Inside).is_err());
+        assert!(resolve(0, 0, Placement::After).is_err());
+    }
+
+    #[test]
+    fn expanded_node() {
+        let tree = snapshot(5);
+        assert_eq!(
+            tree.resolve(&0, Placement::After, 0.0, true, Some(20.0), None),
+            Ok(tree_move(5, Some(0), 0))
+        );
+    }
+
+    #[test]
+    fn max_depth() {
+        let tree = snapshot(0);
+        // The children of the moved node count towards the depth.
+        assert!(tree
+            .resolve(&4, Placement::Inside, 0.0, false, None, Some(2))
+            .is_err());
+        assert_eq!(
+            tree.resolve(&4, Placement::Inside, 0.0, false, None, Some(3)),
+            Ok(tree_move(0, Some(4), 0))
+        );
+    }
+
+    #[test]
+    fn indent_levels() {
+        let tree = snapshot(5);
+        let resolve = |offset| tree.resolve(&3, Placement::After, offset, false, Some(20.0), None);
+        // Without moving sideways, the node keeps its level and leaves the parents of the target.
+        assert_eq!(resolve(0.0), Ok(tree_move(5, None, 1)));
+        assert_eq!(resolve(-40.0), Ok(tree_move(5, None, 1)));
+        assert_eq!(resolve(18.0), Ok(tree_move(5, Some(0), 2)));
+        assert_eq!(resolve(40.0), Ok(tree_move(5, Some(2), 1)));
+        assert_eq!(resolve(100.0), Ok(tree_move(5, Some(3), 0)));
+
+        // Nodes are only moved out of parents the target is the last node of.
+        let tree = snapshot(4);
+        assert_eq!(
+            tree.resolve(&1, Placement::After, 0.0, false, Some(20.0), None),
+            Ok(tree_move(4, Some(0), 1))
+        );
+    }
+
+    #[test]
+    fn last_child_ignores_dragged_node() {
+        let tree = snapshot(2);
+        assert_eq!(
+            tree.resolve(&1, Placement::After, -20.0, false, Some(20.0), None),
+            Ok(tree_move(2, None, 1))
+        );
+        assert_eq!(
+            tree.resolve(&1, Placement::After, 0.0, false, Some(20.0), None),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn renders_on_the_server() {
+        let html = render_to_string(|cx| {
+            let tree = create_tree(
+                cx,
+                |node: &u32| NODES.iter().find(|(key, _)| key == node).and_then(|n| n.1),
+                |parent| {
+                    NODES
+                        .iter()
+                        .filter(|(_, node_parent)| node_parent.as_ref() == parent)
+                        .map(|(node, _)| *node)
+                        .collect()
+                },
+            )
+            .indent(20.0)
+            .build();
+            let nodes = NODES.iter().map(|(node, _)| *node).collect::<Vec<_>>();
+            View::new_fragment(
+                nodes
+                    .into_iter()
+                    .map(|key| {
+                        let node = tree.node(cx, key);
+                        view! { cx, li(ref = node) { (key) } }
+                    })
+                    .collect(),
+            )
+        });
+        assert_eq!(html.matches("<li").count(), NODES.len());
+    }
+}